@@ -0,0 +1,9 @@
+#[allow(dead_code, unused_imports)]
+mod solution {
+    include!("../src/bin/03.rs");
+
+    advent_of_code::benchmark!(part_one, part_two);
+}
+
+criterion::criterion_group!(benches, solution::benchmark);
+criterion::criterion_main!(benches);
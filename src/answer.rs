@@ -0,0 +1,80 @@
+use std::any::type_name;
+use std::fmt::Display;
+use std::sync::Mutex;
+
+/// Arbitrary precision integers for answers that do not fit into a primitive.
+///
+/// Both types implement [`Display`], so they can be returned from `part_one` / `part_two`
+/// like any other answer.
+pub use num_bigint::{BigInt, BigUint};
+
+static NARROWING_WARNINGS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+/// Converts an intermediate value into a narrower answer type.
+///
+/// Unlike `value.try_into().ok()`, a failed conversion is not silent: the runner prints a warning
+/// after the part has finished so an overflow is not mistaken for an unsolved part.
+///
+/// ```
+/// # use advent_of_code::narrow;
+/// let answer: Option<u32> = narrow(42_usize);
+/// assert_eq!(answer, Some(42));
+/// ```
+pub fn narrow<T, U>(value: T) -> Option<U>
+where
+    T: Copy + Display + TryInto<U>,
+{
+    match value.try_into() {
+        Ok(narrowed) => Some(narrowed),
+        Err(_) => {
+            let warning = format!(
+                "{value} does not fit into `{}` (converted from `{}`).",
+                type_name::<U>(),
+                type_name::<T>()
+            );
+
+            let mut warnings = NARROWING_WARNINGS.lock().unwrap();
+            // benchmarks run the same conversion many times, only keep distinct warnings.
+            if !warnings.contains(&warning) {
+                warnings.push(warning);
+            }
+
+            None
+        }
+    }
+}
+
+/// Returns and clears all warnings recorded by [`narrow`].
+pub(crate) fn take_narrowing_warnings() -> Vec<String> {
+    std::mem::take(&mut *NARROWING_WARNINGS.lock().unwrap())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{narrow, take_narrowing_warnings, BigInt};
+
+    #[test]
+    fn narrows_values_in_range() {
+        assert_eq!(narrow::<u64, u32>(42), Some(42));
+        assert_eq!(narrow::<i64, i32>(-42), Some(-42));
+        assert_eq!(narrow::<i128, u8>(255), Some(255));
+    }
+
+    #[test]
+    fn records_lossy_conversions() {
+        assert_eq!(narrow::<u64, u32>(u64::from(u32::MAX) + 1), None);
+        assert_eq!(narrow::<i32, u32>(-1), None);
+        assert_eq!(narrow::<i32, u32>(-1), None);
+
+        let warnings = take_narrowing_warnings();
+        assert!(warnings
+            .contains(&"4294967296 does not fit into `u32` (converted from `u64`).".to_string()));
+        assert_eq!(warnings.iter().filter(|w| w.starts_with("-1 ")).count(), 1);
+    }
+
+    #[test]
+    fn displays_big_integers() {
+        let big = BigInt::from(u128::MAX) * 2_u8 + 1_u8;
+        assert_eq!(big.to_string(), "680564733841876926926749214863536422911");
+    }
+}
@@ -200,12 +200,9 @@ fn iterate_around_with_row_skip(
 }
 
 pub fn part_one(input: &str) -> Option<u32> {
-    let (grid, _symbols, numbers) = parse(input, |_| true);
-    #[cfg(debug_assertions)]
-    {
-        println!("{}", numbers);
-        println!("{}", _symbols);
-    }
+    let (grid, symbols, numbers) = parse(input, |_| true);
+    advent_of_code::debug!("{}", numbers);
+    advent_of_code::debug!("{}", symbols);
 
     grid.iter()
         .filter(|(_, cell)| {
@@ -284,10 +284,9 @@ pub fn part_two(input: &str) -> Option<u32> {
             .iter()
             .sorted_by(|(a, _), (b, _)| sort_hands(a, b).unwrap())
             .enumerate()
-            .inspect(|(_, (_hand, _))| {
-                #[cfg(debug_assertions)]
-                if _hand.0.contains(&Card::Joker) {
-                    println!("{:?}", _hand);
+            .inspect(|(_, (hand, _))| {
+                if hand.0.contains(&Card::Joker) {
+                    advent_of_code::trace!("{:?}", hand);
                 }
             })
             .map(|(i, (_, bid))| bid * (i as u32 + 1))
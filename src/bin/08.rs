@@ -116,8 +116,7 @@ pub fn part_two(input: &str) -> Option<u64> {
         })
         .collect::<Vec<_>>();
 
-    #[cfg(debug_assertions)]
-    println!("cycle lengths: {:?}", cycle_length);
+    advent_of_code::debug!("cycle lengths: {:?}", cycle_length);
 
     Some(
         cycle_length
@@ -145,6 +144,8 @@ mod tests {
         assert_eq!(result, Some(6));
     }
 
+    advent_of_code::example_tests!(repeated_instructions);
+
     #[test]
     fn test_gcd() {
         assert_eq!(gcd(2, 4), 2);
@@ -33,8 +33,7 @@ fn solve(input: &str) -> Vec<Vec<(Vec<i32>, bool)>> {
                 diff_seqs.push((diff_seq, zero));
             }
 
-            #[cfg(debug_assertions)]
-            println!(
+            advent_of_code::trace!(
                 "{}",
                 diff_seqs
                     .iter()
@@ -65,8 +64,7 @@ pub fn part_one(input: &str) -> Option<i32> {
                 this_diff
             });
 
-            #[cfg(debug_assertions)]
-            println!("next: {}\n", next);
+            advent_of_code::trace!("next: {}", next);
 
             next
         })
@@ -87,8 +85,7 @@ pub fn part_two(input: &str) -> Option<i32> {
                 this_diff
             });
 
-            #[cfg(debug_assertions)]
-            println!("0th: {}\n", zeroth);
+            advent_of_code::trace!("0th: {}", zeroth);
 
             zeroth
         })
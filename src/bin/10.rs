@@ -1,6 +1,6 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
-use advent_of_code::Grid;
+use advent_of_code::{template::render, Grid, Rgb};
 
 advent_of_code::solution!(10);
 
@@ -168,8 +168,7 @@ fn parse(input: &str) -> (Grid<FieldCell>, (usize, usize)) {
         }
     }
 
-    #[cfg(debug_assertions)]
-    println!("{}", grid);
+    advent_of_code::trace!("{}", grid);
 
     (grid, start.unwrap())
 }
@@ -246,7 +245,10 @@ pub fn part_two(input: &str) -> Option<u32> {
         grid.set(start_x, start_y, start_corner);
     }
 
-    let mut enclosed_cells = 0;
+    // the cells are only collected to colour them in the rendered image.
+    let rendering = render::enabled();
+    let mut enclosed = 0;
+    let mut enclosed_cells = HashSet::new();
 
     lazy_static::lazy_static! {
         // OPENING_CORNER_PAIRS defines pairs which, if observed in succession on the path (in
@@ -288,7 +290,11 @@ pub fn part_two(input: &str) -> Option<u32> {
 
             if !on_path {
                 if intersections % 2 == 1 {
-                    enclosed_cells += 1;
+                    enclosed += 1;
+
+                    if rendering {
+                        enclosed_cells.insert((x, y));
+                    }
                 }
             } else {
                 match cell {
@@ -323,7 +329,20 @@ pub fn part_two(input: &str) -> Option<u32> {
         }
     }
 
-    Some(enclosed_cells)
+    render::output(
+        &grid
+            .render(|cell, _| {
+                if enclosed_cells.contains(cell) {
+                    Rgb::GREEN
+                } else {
+                    Rgb::BLACK
+                }
+            })
+            .scale(6)
+            .path(path.iter().chain(path.first()).copied(), Rgb::YELLOW),
+    );
+
+    Some(enclosed)
 }
 
 #[cfg(test)]
@@ -343,4 +362,6 @@ mod tests {
         ));
         assert_eq!(result, Some(10));
     }
+
+    advent_of_code::example_tests!(simple_loop, enclosed, squeezed, larger);
 }
@@ -1,7 +1,7 @@
 use advent_of_code::Grid;
 use itertools::Itertools;
 
-advent_of_code::solution!(11);
+advent_of_code::solution!(11, expansion: usize = 1_000_000);
 
 #[derive(Clone, Copy, PartialEq, Eq)]
 enum Cell {
@@ -85,11 +85,8 @@ fn galaxy_pairwise_distances<CoordMapFn: Fn(&(usize, usize)) -> (usize, usize)>(
             let (x1, y1) = mapper(a);
             let (x2, y2) = mapper(b);
 
-            #[cfg(debug_assertions)]
-            {
-                println!("originally: ({}, {}) -> ({}, {})", a.0, a.1, b.0, b.1);
-                println!("mapped: ({}, {}) -> ({}, {})", x1, y1, x2, y2);
-            }
+            advent_of_code::trace!("originally: ({}, {}) -> ({}, {})", a.0, a.1, b.0, b.1);
+            advent_of_code::trace!("mapped: ({}, {}) -> ({}, {})", x1, y1, x2, y2);
 
             ((x1 as i64 - x2 as i64).abs() + (y1 as i64 - y2 as i64).abs()) as u64
         })
@@ -104,7 +101,7 @@ pub fn part_one(input: &str) -> Option<u32> {
 
 // big numbers: this one overflows a u32
 fn part_two(input: &str) -> Option<u64> {
-    Some(compute_with_expansion(input, 1_000_000))
+    Some(compute_with_expansion(input, params().expansion))
 }
 
 fn compute_with_expansion(input: &str, expansion_size: usize) -> u64 {
@@ -131,6 +128,7 @@ fn compute_with_expansion(input: &str, expansion_size: usize) -> u64 {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code::template::params;
 
     #[test]
     fn test_part_one() {
@@ -143,11 +141,10 @@ mod tests {
         [(10, 1030), (100, 8410)]
             .iter()
             .for_each(|(expansion_size, expected)| {
-                let result = compute_with_expansion(
-                    &advent_of_code::template::read_file("examples", DAY),
-                    *expansion_size,
-                );
-                assert_eq!(result, *expected, "expansion size {}", expansion_size);
+                let result = params::with(&[("expansion", expansion_size.to_string())], || {
+                    part_two(&advent_of_code::template::read_file("examples", DAY))
+                });
+                assert_eq!(result, Some(*expected), "expansion size {}", expansion_size);
             });
     }
 }
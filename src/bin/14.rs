@@ -1,7 +1,7 @@
 use std::collections::hash_map::DefaultHasher;
 use std::hash::Hasher;
 
-use advent_of_code::Grid;
+use advent_of_code::{template::visualize, Grid};
 
 advent_of_code::solution!(14);
 
@@ -126,9 +126,12 @@ fn score_grid(g: &Grid<Rock>) -> usize {
 
 pub fn part_one(input: &str) -> Option<u32> {
     let grid: Grid<Rock> = Grid::parse(input).unwrap();
+    visualize::record(&grid);
+
     let rolled_grid = roll_grid(&grid, Direction::North);
+    visualize::record(&rolled_grid);
 
-    score_grid(&rolled_grid).try_into().ok()
+    advent_of_code::narrow(score_grid(&rolled_grid))
 }
 
 fn hash_grid(g: &Grid<Rock>) -> u64 {
@@ -160,6 +163,7 @@ pub fn part_two(input: &str) -> Option<u32> {
         .iter()
         .for_each(|d| {
             start_grid = roll_grid(&start_grid, *d);
+            visualize::record(&start_grid);
         });
 
         let hash = hash_grid(&start_grid);
@@ -179,7 +183,7 @@ pub fn part_two(input: &str) -> Option<u32> {
     let steps_remaining = (1_000_000_000 - cycle_start) % len;
     let (_, _, score) = grids_seen[cycle_start + steps_remaining - 1];
 
-    score.try_into().ok()
+    advent_of_code::narrow(score)
 }
 
 #[cfg(test)]
@@ -1,6 +1,9 @@
-use std::collections::HashSet;
+use std::{collections::HashSet, fmt::Display};
 
-use advent_of_code::Grid;
+use advent_of_code::{
+    template::{trace, visualize},
+    Grid, Rgb, Style,
+};
 use itertools::Itertools;
 
 advent_of_code::solution!(16);
@@ -74,8 +77,7 @@ fn intersect_beam_with_cell(
     let ((x, y), dir) = beam;
     let cell = grid.get(x, y).unwrap();
 
-    #[cfg(debug_assertions)]
-    println!("intersect: {:?} {:?}", beam, cell);
+    advent_of_code::trace!("intersect: {:?} {:?}", beam, cell);
 
     match (cell, dir) {
         (Cell::Empty, _) => None,
@@ -163,11 +165,34 @@ fn intersect_beam_with_cell(
     }
 }
 
-fn energised(grid: &Grid<Cell>, initial: ((usize, usize), BeamDirection)) -> u32 {
+fn fmt_energised<'a>(
+    grid: &'a Grid<Cell>,
+    energised: &'a HashSet<((usize, usize), BeamDirection)>,
+    colours: bool,
+) -> impl Display + 'a {
+    grid.fmt_with_styles(colours, |&(x, y), _| {
+        if energised.contains(&((x, y), BeamDirection::Left))
+            || energised.contains(&((x, y), BeamDirection::Right))
+            || energised.contains(&((x, y), BeamDirection::Up))
+            || energised.contains(&((x, y), BeamDirection::Down))
+        {
+            Some(Style::new().bg(Rgb::YELLOW).bold().glyph('#'))
+        } else {
+            None
+        }
+    })
+}
+
+// record_waves records a visualisation frame for each wave of beams. Part two energises the grid
+// from every edge, so it only records the final state of each start.
+fn energised(
+    grid: &Grid<Cell>,
+    initial: ((usize, usize), BeamDirection),
+    record_waves: bool,
+) -> u32 {
     let mut energised: HashSet<((usize, usize), BeamDirection)> = HashSet::new();
 
-    #[cfg(debug_assertions)]
-    println!("{}", grid);
+    advent_of_code::trace!("{}", grid);
 
     let mut beams = vec![initial];
     let mut new_beams = vec![];
@@ -191,8 +216,7 @@ fn energised(grid: &Grid<Cell>, initial: ((usize, usize), BeamDirection)) -> u32
                 continue;
             }
 
-            #[cfg(debug_assertions)]
-            println!("{:?} {:?}", beam, dir);
+            advent_of_code::trace!("{:?} {:?}", beam, dir);
 
             let coords_it: Box<dyn Iterator<Item = (usize, usize)>> = match dir {
                 BeamDirection::Left => {
@@ -214,39 +238,28 @@ fn energised(grid: &Grid<Cell>, initial: ((usize, usize), BeamDirection)) -> u32
             };
 
             for (x, y) in coords_it {
-                #[cfg(debug_assertions)]
-                println!("it: {:?} {:?}", (x, y), dir);
+                advent_of_code::trace!("it: {:?} {:?}", (x, y), dir);
                 energised.insert(((x, y), dir));
 
                 if let Some(add_beams) = intersect_beam_with_cell(&grid, ((x, y), dir)) {
-                    #[cfg(debug_assertions)]
-                    println!("it x: {:?}", add_beams);
+                    advent_of_code::trace!("it x: {:?}", add_beams);
                     new_beams.extend(add_beams);
                     break;
                 }
             }
         }
 
-        #[cfg(debug_assertions)]
-        println!("new beams: {:?}", new_beams);
+        advent_of_code::debug!("new beams: {:?}", new_beams);
         beams.extend(new_beams.drain(..));
-    }
 
-    #[cfg(debug_assertions)]
-    for y in 0..grid.height() {
-        for x in 0..grid.width() {
-            if energised.contains(&((x, y), BeamDirection::Left))
-                || energised.contains(&((x, y), BeamDirection::Right))
-                || energised.contains(&((x, y), BeamDirection::Up))
-                || energised.contains(&((x, y), BeamDirection::Down))
-            {
-                print!("X");
-            } else {
-                print!(".");
-            }
+        if record_waves {
+            visualize::record(fmt_energised(grid, &energised, visualize::colours()));
         }
+    }
 
-        print!("\n");
+    advent_of_code::debug!("{}", fmt_energised(grid, &energised, trace::colours()));
+    if !record_waves {
+        visualize::record(fmt_energised(grid, &energised, visualize::colours()));
     }
 
     energised
@@ -258,7 +271,7 @@ fn energised(grid: &Grid<Cell>, initial: ((usize, usize), BeamDirection)) -> u32
 
 pub fn part_one(input: &str) -> Option<u32> {
     let grid: Grid<Cell> = Grid::parse(input).unwrap();
-    Some(energised(&grid, ((0, 0), BeamDirection::Right)))
+    Some(energised(&grid, ((0, 0), BeamDirection::Right), true))
 }
 
 pub fn part_two<'a>(input: &'a str) -> Option<u32> {
@@ -268,22 +281,22 @@ pub fn part_two<'a>(input: &'a str) -> Option<u32> {
 
     let a = (0..width)
         .zip(std::iter::repeat(0))
-        .map(|(x, y)| energised(&grid, ((x, y), BeamDirection::Down)))
+        .map(|(x, y)| energised(&grid, ((x, y), BeamDirection::Down), false))
         .max()
         .unwrap_or(0);
     let b = (0..width)
         .zip(std::iter::repeat(height - 1))
-        .map(|(x, y)| energised(&grid, ((x, y), BeamDirection::Up)))
+        .map(|(x, y)| energised(&grid, ((x, y), BeamDirection::Up), false))
         .max()
         .unwrap_or(0);
     let c = std::iter::repeat(0)
         .zip(0..height)
-        .map(|(x, y)| energised(&grid, ((x, y), BeamDirection::Right)))
+        .map(|(x, y)| energised(&grid, ((x, y), BeamDirection::Right), false))
         .max()
         .unwrap_or(0);
     let d = std::iter::repeat(width - 1)
         .zip(0..height)
-        .map(|(x, y)| energised(&grid, ((x, y), BeamDirection::Left)))
+        .map(|(x, y)| energised(&grid, ((x, y), BeamDirection::Left), false))
         .max()
         .unwrap_or(0);
 
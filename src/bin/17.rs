@@ -1,4 +1,4 @@
-use advent_of_code::Grid;
+use advent_of_code::{template::trace, Grid, Rgb, Style};
 use itertools::Itertools;
 use priority_queue::PriorityQueue;
 
@@ -128,13 +128,16 @@ fn get_losses(grid: &Grid<u32>, (min, max): (usize, usize)) -> u32 {
         path
     };
 
-    #[cfg(debug_assertions)]
-    for y in 0..grid.height() {
-        for x in 0..grid.width() {
-            print!("{}", if path.contains(&(x, y)) { "O" } else { "." });
-        }
-        println!();
-    }
+    advent_of_code::debug!(
+        "{}",
+        grid.fmt_with_styles(trace::colours(), |cell, _| {
+            Some(if path.contains(cell) {
+                Style::new().fg(Rgb::RED).bold().glyph('O')
+            } else {
+                Style::new().glyph('.')
+            })
+        })
+    );
 
     (0..(4 * max))
         .filter_map(|off| {
@@ -149,12 +152,12 @@ fn get_losses(grid: &Grid<u32>, (min, max): (usize, usize)) -> u32 {
 }
 
 pub fn part_one(input: &str) -> Option<u32> {
-    let grid = Grid::parse_with_parser(input, |c| c.to_digit(10).unwrap() as u32).unwrap();
+    let grid = Grid::parse_with_parser(0, input, |c| c.to_digit(10).unwrap() as u32).unwrap();
     Some(get_losses(&grid, (0, 4)))
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
-    let grid = Grid::parse_with_parser(input, |c| c.to_digit(10).unwrap() as u32).unwrap();
+    let grid = Grid::parse_with_parser(0, input, |c| c.to_digit(10).unwrap() as u32).unwrap();
     Some(get_losses(&grid, (4, 11)))
 }
 
@@ -161,12 +161,10 @@ fn get_combos<'a>(
     let mut combos = 0;
     let flow = flows.get(start).unwrap();
 
-    #[cfg(debug_assertions)]
-    println!("starting at {}", start);
+    advent_of_code::trace!("starting at {}", start);
 
     for (op, next) in &flow.0 {
-        #[cfg(debug_assertions)]
-        println!("{}: checking {} {:?} {}", start, next, op, next == &"A");
+        advent_of_code::trace!("{}: checking {} {:?} {}", start, next, op, next == &"A");
         let mut part = current.clone();
 
         if let &Some((score, op, value)) = op {
@@ -187,22 +185,17 @@ fn get_combos<'a>(
         }
 
         if next == &"A" {
-            #[cfg(debug_assertions)]
-            println!("found a combo accepting: {:?}", part);
+            advent_of_code::trace!("found a combo accepting: {:?}", part);
 
             combos += [part.0, part.1, part.2, part.3]
                 .into_iter()
                 .map(|(min, max)| max.checked_sub(min).unwrap() as u64)
-                .inspect(|_c| {
-                    #[cfg(debug_assertions)]
-                    println!("{}: {}", start, _c);
-                })
+                .inspect(|c| advent_of_code::trace!("{}: {}", start, c))
                 .product::<u64>();
         } else if next == &"R" {
             continue;
         } else {
-            #[cfg(debug_assertions)]
-            println!("recursing into {} {:?}", next, part);
+            advent_of_code::trace!("recursing into {} {:?}", next, part);
             combos += get_combos(part, flows, next);
         }
     }
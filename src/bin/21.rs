@@ -1,8 +1,8 @@
 use std::{collections::HashSet, fmt::Display};
 
-use advent_of_code::Grid;
+use advent_of_code::{template::visualize, Grid};
 
-advent_of_code::solution!(21);
+advent_of_code::solution!(21, steps: usize = 64);
 
 #[derive(Clone, PartialEq)]
 enum Cell {
@@ -42,8 +42,8 @@ fn solve_part_one(input: &str, needed_steps: usize) -> u32 {
     let grid: Grid<Cell> = Grid::parse(input).unwrap();
     let start_cell = grid.iter().find(|(_, c)| c == &Cell::Start).unwrap().0;
 
-    println!("{}", grid);
-    println!("{:?}", start_cell);
+    advent_of_code::trace!("{}", grid);
+    advent_of_code::debug!("start: {:?}", start_cell);
 
     let mut q = HashSet::new();
     q.insert(start_cell);
@@ -63,39 +63,38 @@ fn solve_part_one(input: &str, needed_steps: usize) -> u32 {
 
         q.clear();
         q.extend(next_q.drain(..));
-        println!(
-            "{}",
-            grid.fmt_with_overrides(|&(x, y)| {
-                if q.contains(&(x, y)) {
-                    Some('O')
-                } else {
-                    None
-                }
-            })
-        );
+
+        let reached =
+            grid.fmt_with_overrides(|&(x, y)| if q.contains(&(x, y)) { Some('O') } else { None });
+        advent_of_code::trace!("{}", reached);
+        visualize::record(&reached);
     }
 
-    println!("{:?}", q);
+    advent_of_code::debug!("{:?}", q);
 
     q.len() as u32
 }
 
 pub fn part_one(input: &str) -> Option<u32> {
-    Some(solve_part_one(input, 64))
+    Some(solve_part_one(input, params().steps))
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
+// big numbers: the number of reachable plots overflows a u32
+pub fn part_two(_input: &str) -> Option<u64> {
     None
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code::template::params;
 
     #[test]
     fn test_part_one() {
-        let result = solve_part_one(&advent_of_code::template::read_file("examples", DAY), 6);
-        assert_eq!(result, 16);
+        let result = params::with(&[("steps", "6")], || {
+            part_one(&advent_of_code::template::read_file("examples", DAY))
+        });
+        assert_eq!(result, Some(16));
     }
 
     #[test]
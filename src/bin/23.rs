@@ -16,7 +16,10 @@ use std::collections::{HashMap, HashSet, VecDeque};
 /// the correct answer because a long sequence of consecutive slides could convince the former
 /// algorithm that it is the longest path, while there is actually a longer path with more actual
 /// steps. The puzzle input does not seem to test this.
-use advent_of_code::Grid;
+use advent_of_code::{
+    template::{render, trace},
+    Grid, Rgb, Style,
+};
 
 advent_of_code::solution!(23);
 
@@ -128,7 +131,7 @@ fn solve(input: &str, enable_slopes: bool) -> Option<u32> {
     })
     .unwrap();
 
-    println!("{}", grid);
+    advent_of_code::trace!("{}", grid);
 
     let mut q = VecDeque::new();
 
@@ -192,17 +195,31 @@ fn solve(input: &str, enable_slopes: bool) -> Option<u32> {
         }
     }
 
-    println!(
+    advent_of_code::debug!(
         "{}",
-        grid.fmt_with_overrides(|cell| {
+        grid.fmt_with_styles(trace::colours(), |cell, _| {
             longest_visits
                 .as_ref()
                 .is_some_and(|longest_visits| longest_visits.contains(cell))
-                .then(|| 'O')
+                .then(|| Style::new().bg(Rgb::RED).glyph('O'))
         })
     );
 
-    println!("{}", longest_visits.as_ref().unwrap().len());
+    advent_of_code::info!(
+        "longest path visits {} cells",
+        longest_visits.as_ref().unwrap().len()
+    );
+
+    render::output(
+        &grid
+            .render(|_, cell| match cell {
+                Path => Rgb::WHITE,
+                Forest => Rgb::GREEN,
+                Slope(_) => Rgb::BLUE,
+            })
+            .scale(6)
+            .path(longest_visits.unwrap(), Rgb::RED),
+    );
 
     Some(longest as u32 - 1)
 }
@@ -1,5 +1,8 @@
 use std::fmt::Display;
 
+use crate::template::ANSI_RESET;
+use crate::Style;
+
 pub struct Grid<T> {
     pub data: Vec<T>,
     pub width: usize,
@@ -189,6 +192,81 @@ where
     }
 }
 
+impl<T> Grid<T>
+where
+    T: Clone + Display,
+{
+    /// Formats the grid with a [`Style`] for each cell for which `styles` returns one.
+    /// Colours are only written if `colours` is set, replacement glyphs always are. Whether the
+    /// output supports colours depends on where it is written, see
+    /// [`crate::template::trace::colours`] and [`crate::template::visualize::colours`].
+    pub fn fmt_with_styles<'a, F: Fn(&(usize, usize), &T) -> Option<Style> + 'a>(
+        &'a self,
+        colours: bool,
+        styles: F,
+    ) -> StyledFormatter<'a, T, F> {
+        StyledFormatter {
+            grid: self,
+            styles,
+            colours,
+        }
+    }
+}
+
+pub struct StyledFormatter<'a, T, F: Fn(&(usize, usize), &T) -> Option<Style>> {
+    grid: &'a Grid<T>,
+    styles: F,
+    colours: bool,
+}
+
+impl<T, F> Display for StyledFormatter<'_, T, F>
+where
+    T: Clone + Display,
+    F: Fn(&(usize, usize), &T) -> Option<Style>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // the style of the previous cell, so runs of equally styled cells share escape sequences.
+        let mut active: Option<Style> = None;
+
+        for ((x, y), value) in self.grid {
+            let style = (self.styles)(&(x, y), &value);
+
+            if self.colours {
+                let attributes = style.filter(Style::has_attributes);
+                let changed = match (&active, &attributes) {
+                    (Some(a), Some(b)) => a.fg != b.fg || a.bg != b.bg || a.bold != b.bold,
+                    (None, None) => false,
+                    _ => true,
+                };
+
+                if changed {
+                    if active.is_some() {
+                        write!(f, "{ANSI_RESET}")?;
+                    }
+                    if let Some(attributes) = &attributes {
+                        attributes.write_ansi(f)?;
+                    }
+                    active = attributes;
+                }
+            }
+
+            match style.and_then(|style| style.glyph) {
+                Some(glyph) => write!(f, "{}", glyph)?,
+                None => write!(f, "{}", value)?,
+            }
+
+            if x == self.grid.width - 1 {
+                if active.take().is_some() {
+                    write!(f, "{ANSI_RESET}")?;
+                }
+                writeln!(f)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
 pub struct GridIterator<'a, T> {
     grid: &'a Grid<T>,
     x: usize,
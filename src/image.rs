@@ -0,0 +1,326 @@
+use std::fmt::Write as _;
+use std::path::Path;
+use std::{fs, io};
+
+use crate::Grid;
+
+/// A colour used when rendering a [`Grid`] to an image.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    pub const BLACK: Rgb = Rgb(0, 0, 0);
+    pub const WHITE: Rgb = Rgb(255, 255, 255);
+    pub const RED: Rgb = Rgb(220, 50, 47);
+    pub const GREEN: Rgb = Rgb(133, 153, 0);
+    pub const BLUE: Rgb = Rgb(38, 139, 210);
+    pub const YELLOW: Rgb = Rgb(181, 137, 0);
+    pub const GREY: Rgb = Rgb(128, 128, 128);
+
+    fn to_hex(self) -> String {
+        format!("#{:02x}{:02x}{:02x}", self.0, self.1, self.2)
+    }
+}
+
+/// Image formats a [`GridImage`] can be encoded to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageFormat {
+    /// Binary portable pixmap (`P6`).
+    Ppm,
+    Svg,
+}
+
+impl ImageFormat {
+    /// Determines the format from a file extension, e.g. `day10.svg`.
+    pub fn from_path(path: impl AsRef<Path>) -> Option<Self> {
+        match path.as_ref().extension()?.to_str()? {
+            "ppm" => Some(ImageFormat::Ppm),
+            "svg" => Some(ImageFormat::Svg),
+            _ => None,
+        }
+    }
+}
+
+impl<T> Grid<T>
+where
+    T: Clone,
+{
+    /// Renders the grid to an image, colouring each cell with the colour returned by `colour`.
+    ///
+    /// ```
+    /// # use advent_of_code::{Grid, Rgb};
+    /// let grid: Grid<char> = Grid::parse_with_parser('.', "#.\n.#", |c| c).unwrap();
+    /// let image = grid.render(|_, &c| if c == '#' { Rgb::BLACK } else { Rgb::WHITE }).scale(4);
+    /// assert_eq!(image.dimensions(), (8, 8));
+    /// ```
+    pub fn render<F: Fn(&(usize, usize), &T) -> Rgb>(&self, colour: F) -> GridImage<'_, T, F> {
+        GridImage {
+            grid: self,
+            colour,
+            scale: 1,
+            paths: vec![],
+        }
+    }
+}
+
+/// Points of a path, collected only when the image is encoded.
+type Points<'a> = Box<dyn Fn() -> Vec<(usize, usize)> + 'a>;
+
+pub struct GridImage<'a, T, F: Fn(&(usize, usize), &T) -> Rgb> {
+    grid: &'a Grid<T>,
+    colour: F,
+    scale: usize,
+    paths: Vec<(Points<'a>, Rgb)>,
+}
+
+impl<'a, T, F> GridImage<'a, T, F>
+where
+    T: Clone,
+    F: Fn(&(usize, usize), &T) -> Rgb,
+{
+    /// Sets the width and height of a single cell in pixels.
+    #[must_use]
+    pub fn scale(mut self, scale: usize) -> Self {
+        self.scale = scale.max(1);
+        self
+    }
+
+    /// Draws a line through the centres of the given cells on top of the grid. The points are
+    /// only iterated when the image is encoded.
+    #[must_use]
+    pub fn path<I>(mut self, points: I, colour: Rgb) -> Self
+    where
+        I: IntoIterator<Item = (usize, usize)>,
+        I::IntoIter: Clone + 'a,
+    {
+        let points = points.into_iter();
+        self.paths
+            .push((Box::new(move || points.clone().collect()), colour));
+        self
+    }
+
+    /// Width and height of the rendered image in pixels.
+    pub fn dimensions(&self) -> (usize, usize) {
+        (self.grid.width * self.scale, self.grid.height * self.scale)
+    }
+
+    fn path_width(&self) -> usize {
+        (self.scale / 3).max(1)
+    }
+
+    fn cell_centre(&self, (x, y): (usize, usize)) -> (usize, usize) {
+        (
+            x * self.scale + self.scale / 2,
+            y * self.scale + self.scale / 2,
+        )
+    }
+
+    /// Encodes the image as a binary PPM (`P6`) file.
+    pub fn to_ppm(&self) -> Vec<u8> {
+        let (width, height) = self.dimensions();
+        let mut pixels = vec![Rgb::BLACK; width * height];
+
+        for (position, value) in self.grid {
+            let colour = (self.colour)(&position, &value);
+            let (x0, y0) = (position.0 * self.scale, position.1 * self.scale);
+
+            for y in y0..y0 + self.scale {
+                pixels[y * width + x0..y * width + x0 + self.scale].fill(colour);
+            }
+        }
+
+        let path_width = self.path_width();
+        let mut draw = |x: usize, y: usize, colour: Rgb| {
+            let offset = path_width / 2;
+            for py in y.saturating_sub(offset)..(y + path_width - offset).min(height) {
+                for px in x.saturating_sub(offset)..(x + path_width - offset).min(width) {
+                    pixels[py * width + px] = colour;
+                }
+            }
+        };
+
+        for (points, colour) in &self.paths {
+            let points = points();
+
+            for segment in points.windows(2) {
+                let (x0, y0) = self.cell_centre(segment[0]);
+                let (x1, y1) = self.cell_centre(segment[1]);
+                let steps = x0.abs_diff(x1).max(y0.abs_diff(y1)).max(1);
+
+                for step in 0..=steps {
+                    let lerp = |a: usize, b: usize| {
+                        (a as f64 + (b as f64 - a as f64) * step as f64 / steps as f64).round()
+                            as usize
+                    };
+                    draw(lerp(x0, x1), lerp(y0, y1), *colour);
+                }
+            }
+
+            if let [point] = points[..] {
+                let (x, y) = self.cell_centre(point);
+                draw(x, y, *colour);
+            }
+        }
+
+        let mut ppm = format!("P6\n{width} {height}\n255\n").into_bytes();
+        ppm.extend(pixels.iter().flat_map(|Rgb(r, g, b)| [*r, *g, *b]));
+        ppm
+    }
+
+    /// Encodes the image as an SVG document. Runs of equally coloured cells are merged into one
+    /// rectangle to keep the output small for large grids.
+    pub fn to_svg(&self) -> String {
+        let (width, height) = self.dimensions();
+        let scale = self.scale;
+
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" viewBox=\"0 0 {width} {height}\" shape-rendering=\"crispEdges\">\n"
+        );
+
+        for y in 0..self.grid.height {
+            let mut x = 0;
+
+            while x < self.grid.width {
+                let colour = (self.colour)(&(x, y), self.grid.get(x, y).unwrap());
+                let run = (x + 1..self.grid.width)
+                    .take_while(|&x| (self.colour)(&(x, y), self.grid.get(x, y).unwrap()) == colour)
+                    .count()
+                    + 1;
+
+                let _ = writeln!(
+                    svg,
+                    "  <rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{scale}\" fill=\"{}\"/>",
+                    x * scale,
+                    y * scale,
+                    run * scale,
+                    colour.to_hex()
+                );
+
+                x += run;
+            }
+        }
+
+        for (points, colour) in &self.paths {
+            let points = points()
+                .into_iter()
+                .map(|point| {
+                    let (x, y) = self.cell_centre(point);
+                    format!("{x},{y}")
+                })
+                .collect::<Vec<_>>()
+                .join(" ");
+
+            let _ = writeln!(
+                svg,
+                "  <polyline points=\"{points}\" fill=\"none\" stroke=\"{}\" stroke-width=\"{}\" stroke-linecap=\"round\" stroke-linejoin=\"round\"/>",
+                colour.to_hex(),
+                self.path_width()
+            );
+        }
+
+        svg.push_str("</svg>\n");
+        svg
+    }
+
+    /// Encodes the image in the given format.
+    pub fn encode(&self, format: ImageFormat) -> Vec<u8> {
+        match format {
+            ImageFormat::Ppm => self.to_ppm(),
+            ImageFormat::Svg => self.to_svg().into_bytes(),
+        }
+    }
+
+    /// Writes the image to `path`. The format is determined by the file extension.
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let format = ImageFormat::from_path(&path).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "unsupported image format, expecting a .ppm or .svg file",
+            )
+        })?;
+
+        fs::write(path, self.encode(format))
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{ImageFormat, Rgb};
+    use crate::Grid;
+
+    fn get_grid() -> Grid<char> {
+        Grid::parse_with_parser('.', "#..\n.#.", |c| c).unwrap()
+    }
+
+    fn colour(_: &(usize, usize), c: &char) -> Rgb {
+        if *c == '#' {
+            Rgb::BLACK
+        } else {
+            Rgb::WHITE
+        }
+    }
+
+    #[test]
+    fn renders_ppm() {
+        let grid = get_grid();
+        let ppm = grid.render(colour).to_ppm();
+
+        let header = b"P6\n3 2\n255\n";
+        assert_eq!(&ppm[..header.len()], header);
+        assert_eq!(
+            &ppm[header.len()..],
+            &[0, 0, 0, 255, 255, 255, 255, 255, 255, 255, 255, 255, 0, 0, 0, 255, 255, 255]
+        );
+    }
+
+    #[test]
+    fn scales_ppm() {
+        let grid = get_grid();
+        let ppm = grid.render(colour).scale(2).to_ppm();
+
+        let header = b"P6\n6 4\n255\n";
+        assert_eq!(&ppm[..header.len()], header);
+        assert_eq!(ppm.len(), header.len() + 6 * 4 * 3);
+        // second pixel of the first row still belongs to the top-left cell.
+        assert_eq!(&ppm[header.len() + 3..header.len() + 6], &[0, 0, 0]);
+    }
+
+    #[test]
+    fn draws_paths_in_ppm() {
+        let grid = get_grid();
+        let ppm = grid
+            .render(colour)
+            .path([(0, 1), (2, 1)], Rgb::RED)
+            .to_ppm();
+
+        let header_len = b"P6\n3 2\n255\n".len();
+        let row = &ppm[header_len + 9..];
+        assert!(row.chunks(3).all(|pixel| pixel == [220, 50, 47]));
+    }
+
+    #[test]
+    fn renders_svg() {
+        let grid = get_grid();
+        let svg = grid
+            .render(colour)
+            .scale(10)
+            .path([(0, 0), (1, 1)], Rgb::RED)
+            .to_svg();
+
+        assert!(
+            svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"30\" height=\"20\"")
+        );
+        assert!(
+            svg.contains("<rect x=\"10\" y=\"0\" width=\"20\" height=\"10\" fill=\"#ffffff\"/>")
+        );
+        assert!(svg.contains("<polyline points=\"5,5 15,15\" fill=\"none\" stroke=\"#dc322f\""));
+        assert_eq!(svg.matches("<rect").count(), 5);
+    }
+
+    #[test]
+    fn detects_format_from_path() {
+        assert_eq!(ImageFormat::from_path("a/b.ppm"), Some(ImageFormat::Ppm));
+        assert_eq!(ImageFormat::from_path("b.svg"), Some(ImageFormat::Svg));
+        assert_eq!(ImageFormat::from_path("b.png"), None);
+    }
+}
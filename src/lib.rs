@@ -1,6 +1,12 @@
+mod answer;
 mod day;
 mod grid;
+mod image;
+mod style;
 pub mod template;
 
+pub use answer::*;
 pub use day::*;
 pub use grid::*;
+pub use image::*;
+pub use style::*;
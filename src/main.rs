@@ -1,30 +1,63 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve};
+use advent_of_code::template::commands::{
+    all, crossval, dashboard, download, examples, leaderboard, read, render, scaffold, solve,
+    stars, verify,
+};
 use args::{parse, AppArguments};
 
 mod args {
     use std::process;
 
+    use advent_of_code::template::commands::{all, leaderboard, scaffold, solve};
+    use advent_of_code::template::params;
+    use advent_of_code::template::trace::Level;
     use advent_of_code::Day;
 
     pub enum AppArguments {
         Download {
             day: Day,
+            input_set: Option<String>,
+            session_file: Option<String>,
         },
         Read {
             day: Day,
+            part: Option<u8>,
+        },
+        Examples {
+            day: Day,
+            force: bool,
+            dry_run: bool,
         },
         Scaffold {
             day: Day,
+            answer_type: String,
+            template: Option<String>,
+            force: bool,
+            dry_run: bool,
         },
         Solve {
+            day: Day,
+            options: solve::Options,
+        },
+        Render {
             day: Day,
             release: bool,
-            time: bool,
-            submit: Option<u8>,
+            output: Option<String>,
         },
         All {
+            options: all::Options,
+        },
+        Crossval {
             release: bool,
-            time: bool,
+        },
+        Dashboard,
+        Stars,
+        Leaderboard {
+            id: String,
+            options: leaderboard::Options,
+        },
+        Verify {
+            update: bool,
+            encrypt: bool,
         },
     }
 
@@ -33,23 +66,87 @@ mod args {
 
         let app_args = match args.subcommand()?.as_deref() {
             Some("all") => AppArguments::All {
+                options: all::Options {
+                    release: args.contains("--release"),
+                    time: args.contains("--time"),
+                    memory: args.contains("--memory"),
+                    pin: args.opt_value_from_str("--pin")?,
+                },
+            },
+            Some("crossval") => AppArguments::Crossval {
                 release: args.contains("--release"),
-                time: args.contains("--time"),
+            },
+            Some("dashboard") => AppArguments::Dashboard,
+            Some("stars") => AppArguments::Stars,
+            Some("leaderboard") => AppArguments::Leaderboard {
+                options: leaderboard::Options {
+                    file: args.opt_value_from_str("--file")?,
+                    day: args.opt_value_from_str("--day")?,
+                    sort: args.opt_value_from_str("--sort")?.unwrap_or_default(),
+                    refresh: args.contains("--refresh"),
+                    session_file: args.opt_value_from_str("--session-file")?,
+                },
+                id: args.free_from_str()?,
+            },
+            Some("verify") => AppArguments::Verify {
+                update: args.contains("--update"),
+                encrypt: args.contains("--encrypt"),
             },
             Some("download") => AppArguments::Download {
                 day: args.free_from_str()?,
+                input_set: args.opt_value_from_str("--input-set")?,
+                session_file: args.opt_value_from_str("--session-file")?,
             },
             Some("read") => AppArguments::Read {
                 day: args.free_from_str()?,
+                part: args.opt_value_from_str("--part")?,
+            },
+            Some("examples") => AppArguments::Examples {
+                day: args.free_from_str()?,
+                force: args.contains("--force"),
+                dry_run: args.contains("--dry-run"),
             },
             Some("scaffold") => AppArguments::Scaffold {
                 day: args.free_from_str()?,
+                answer_type: args
+                    .opt_value_from_str("--answer")?
+                    .unwrap_or_else(|| scaffold::DEFAULT_ANSWER_TYPE.into()),
+                template: args.opt_value_from_str("--template")?,
+                force: args.contains("--force"),
+                dry_run: args.contains("--dry-run"),
             },
-            Some("solve") => AppArguments::Solve {
+            Some("solve") => {
+                let trace_level: Option<Level> = args.opt_value_from_str("--trace-level")?;
+                let trace_file: Option<String> = args.opt_value_from_str("--trace-file")?;
+                let trace = args.contains("--trace") || trace_file.is_some();
+
+                AppArguments::Solve {
+                    day: args.free_from_str()?,
+                    options: solve::Options {
+                        release: args.contains("--release"),
+                        submit: args.opt_value_from_str("--submit")?,
+                        time: args.contains("--time"),
+                        trace: trace_level.or(trace.then_some(Level::Trace)),
+                        trace_file,
+                        visualize: args.contains("--visualize"),
+                        visualize_export: args.opt_value_from_str("--visualize-export")?,
+                        render: args.opt_value_from_str("--render")?,
+                        watch: args.contains("--watch"),
+                        memory: args.contains("--memory"),
+                        profile: args.opt_value_from_str("--profile")?,
+                        pin: args.opt_value_from_str("--pin")?,
+                        input: args.opt_value_from_str("--input")?,
+                        input_set: args.opt_value_from_str("--input-set")?,
+                        params: args.values_from_fn("--param", |s| {
+                            params::parse_override(s).map(|_| s.to_string())
+                        })?,
+                    },
+                }
+            }
+            Some("render") => AppArguments::Render {
                 day: args.free_from_str()?,
                 release: args.contains("--release"),
-                submit: args.opt_value_from_str("--submit")?,
-                time: args.contains("--time"),
+                output: args.opt_value_from_str("--output")?,
             },
             Some(x) => {
                 eprintln!("Unknown command: {x}");
@@ -77,16 +174,44 @@ fn main() {
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release, time } => all::handle(release, time),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
-            AppArguments::Scaffold { day } => scaffold::handle(day),
-            AppArguments::Solve {
+            AppArguments::All { options } => all::handle(&options),
+            AppArguments::Download {
+                day,
+                input_set,
+                session_file,
+            } => download::handle(day, input_set.as_deref(), session_file.as_deref()),
+            AppArguments::Crossval { release } => crossval::handle(release),
+            AppArguments::Dashboard => dashboard::handle(),
+            AppArguments::Stars => stars::handle(),
+            AppArguments::Leaderboard { id, options } => leaderboard::handle(&id, &options),
+            AppArguments::Verify { update, encrypt } => verify::handle(update, encrypt),
+            AppArguments::Read { day, part } => read::handle(day, part),
+            AppArguments::Examples {
+                day,
+                force,
+                dry_run,
+            } => examples::handle(day, force, dry_run),
+            AppArguments::Scaffold {
+                day,
+                answer_type,
+                template,
+                force,
+                dry_run,
+            } => scaffold::handle(
+                day,
+                &scaffold::Options {
+                    answer_type: &answer_type,
+                    template: template.as_deref(),
+                    force,
+                    dry_run,
+                },
+            ),
+            AppArguments::Solve { day, options } => solve::handle(day, options),
+            AppArguments::Render {
                 day,
                 release,
-                time,
-                submit,
-            } => solve::handle(day, release, time, submit),
+                output,
+            } => render::handle(day, release, output),
         },
     };
 }
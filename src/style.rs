@@ -0,0 +1,122 @@
+use std::env;
+use std::fmt::{self, Write};
+
+use crate::Rgb;
+
+/// Terminal style of a single grid cell, used by [`crate::Grid::fmt_with_styles`].
+///
+/// ```
+/// # use advent_of_code::{Rgb, Style};
+/// let style = Style::new().fg(Rgb::RED).bold();
+/// assert_eq!(style.bold, true);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Style {
+    pub fg: Option<Rgb>,
+    pub bg: Option<Rgb>,
+    pub bold: bool,
+    /// Replaces the character of the cell if set. Otherwise the cell's own character is kept.
+    pub glyph: Option<char>,
+}
+
+impl Style {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[must_use]
+    pub fn fg(mut self, colour: Rgb) -> Self {
+        self.fg = Some(colour);
+        self
+    }
+
+    #[must_use]
+    pub fn bg(mut self, colour: Rgb) -> Self {
+        self.bg = Some(colour);
+        self
+    }
+
+    #[must_use]
+    pub fn bold(mut self) -> Self {
+        self.bold = true;
+        self
+    }
+
+    #[must_use]
+    pub fn glyph(mut self, glyph: char) -> Self {
+        self.glyph = Some(glyph);
+        self
+    }
+
+    /// Whether the style changes how the cell looks apart from its character.
+    pub(crate) fn has_attributes(&self) -> bool {
+        self.fg.is_some() || self.bg.is_some() || self.bold
+    }
+
+    /// Writes the ANSI escape sequence that enables this style.
+    pub(crate) fn write_ansi(&self, f: &mut impl Write) -> fmt::Result {
+        let mut codes = vec![];
+
+        if self.bold {
+            codes.push("1".to_string());
+        }
+
+        if let Some(Rgb(r, g, b)) = self.fg {
+            codes.push(format!("38;2;{r};{g};{b}"));
+        }
+
+        if let Some(Rgb(r, g, b)) = self.bg {
+            codes.push(format!("48;2;{r};{g};{b}"));
+        }
+
+        write!(f, "\x1b[{}m", codes.join(";"))
+    }
+}
+
+/// Returns whether styled output written to a stream should contain colours: the stream is a
+/// terminal and the [`NO_COLOR`](https://no-color.org) environment variable is not set. Pass
+/// `false` for files.
+pub fn colours_enabled(is_terminal: bool) -> bool {
+    let no_colour = env::var_os("NO_COLOR").is_some_and(|x| !x.is_empty());
+    !no_colour && is_terminal
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Style;
+    use crate::{Grid, Rgb};
+
+    #[test]
+    fn writes_ansi_sequences() {
+        let mut s = String::new();
+        Style::new()
+            .bold()
+            .fg(Rgb(1, 2, 3))
+            .bg(Rgb(4, 5, 6))
+            .write_ansi(&mut s)
+            .unwrap();
+        assert_eq!(s, "\x1b[1;38;2;1;2;3;48;2;4;5;6m");
+    }
+
+    #[test]
+    fn formats_grid_with_styles() {
+        let grid: Grid<char> = Grid::parse_with_parser('.', "ab\ncd", |c| c).unwrap();
+        let styles = |&(x, y): &(usize, usize), _: &char| match (x, y) {
+            (0, 0) | (1, 0) => Some(Style::new().bold()),
+            (1, 1) => Some(Style::new().glyph('O')),
+            _ => None,
+        };
+
+        assert_eq!(
+            grid.fmt_with_styles(true, styles).to_string(),
+            "\x1b[1mab\x1b[0m\ncO\n"
+        );
+        assert_eq!(grid.fmt_with_styles(false, styles).to_string(), "ab\ncO\n");
+    }
+
+    #[test]
+    fn glyph_is_not_an_attribute() {
+        assert!(!Style::new().glyph('O').has_attributes());
+        assert!(Style::new().bold().has_attributes());
+    }
+}
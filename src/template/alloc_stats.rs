@@ -0,0 +1,222 @@
+/// Counting global allocator, installed with the `alloc_stats` feature. It wraps the system
+/// allocator and records the heap usage, so that the runner can report the peak heap, the total
+/// bytes allocated and the number of allocations of each part.
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::fmt::Display;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use serde::Serialize;
+
+#[cfg(feature = "alloc_stats")]
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// Bytes that are currently allocated.
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+/// Highest value of `CURRENT` since the last reset.
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+/// `CURRENT` at the last reset, so the peak is reported relative to the heap before the part.
+static BASELINE: AtomicUsize = AtomicUsize::new(0);
+static TOTAL: AtomicUsize = AtomicUsize::new(0);
+static COUNT: AtomicUsize = AtomicUsize::new(0);
+
+pub struct CountingAllocator;
+
+impl CountingAllocator {
+    fn record(grown: usize, shrunk: usize) {
+        if grown > 0 {
+            TOTAL.fetch_add(grown, Ordering::Relaxed);
+            let current = CURRENT.fetch_add(grown, Ordering::Relaxed) + grown;
+            PEAK.fetch_max(current, Ordering::Relaxed);
+        }
+
+        if shrunk > 0 {
+            CURRENT.fetch_sub(shrunk, Ordering::Relaxed);
+        }
+    }
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            COUNT.fetch_add(1, Ordering::Relaxed);
+            Self::record(layout.size(), 0);
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            COUNT.fetch_add(1, Ordering::Relaxed);
+            Self::record(layout.size(), 0);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        Self::record(0, layout.size());
+    }
+
+    /// A reallocation counts as one allocation of the bytes the block grew by.
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            COUNT.fetch_add(1, Ordering::Relaxed);
+            Self::record(
+                new_size.saturating_sub(layout.size()),
+                layout.size().saturating_sub(new_size),
+            );
+        }
+        new_ptr
+    }
+}
+
+/// Heap usage of a part.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct Stats {
+    /// Highest heap usage, relative to the heap before the part.
+    pub peak_bytes: usize,
+    pub total_bytes: usize,
+    pub allocations: usize,
+}
+
+/// Whether the counting allocator is installed.
+pub fn is_enabled() -> bool {
+    cfg!(feature = "alloc_stats")
+}
+
+/// Starts a new measurement.
+pub fn reset() {
+    let current = CURRENT.load(Ordering::Relaxed);
+    BASELINE.store(current, Ordering::Relaxed);
+    PEAK.store(current, Ordering::Relaxed);
+    TOTAL.store(0, Ordering::Relaxed);
+    COUNT.store(0, Ordering::Relaxed);
+}
+
+/// Heap usage since the last [`reset`].
+pub fn stats() -> Stats {
+    Stats {
+        peak_bytes: PEAK
+            .load(Ordering::Relaxed)
+            .saturating_sub(BASELINE.load(Ordering::Relaxed)),
+        total_bytes: TOTAL.load(Ordering::Relaxed),
+        allocations: COUNT.load(Ordering::Relaxed),
+    }
+}
+
+const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+/// Formats bytes with binary units, e.g. `1.5 KiB`.
+pub fn format_bytes(bytes: usize) -> String {
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    let mut value = bytes as f64 / 1024_f64;
+    let mut unit = 0;
+
+    while value >= 1024_f64 && unit < UNITS.len() - 1 {
+        value /= 1024_f64;
+        unit += 1;
+    }
+
+    format!("{value:.1} {}", UNITS[unit])
+}
+
+/// Parses bytes as formatted by [`format_bytes`]. Values with a unit are approximations.
+pub fn parse_bytes(s: &str) -> Option<usize> {
+    let (value, unit) = s.trim().split_once(' ')?;
+
+    if unit == "B" {
+        return value.parse().ok();
+    }
+
+    let exponent = UNITS.iter().position(|x| *x == unit)? + 1;
+    let value = value.parse::<f64>().ok()? * 1024_f64.powi(i32::try_from(exponent).ok()?);
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    Some(value.round() as usize)
+}
+
+impl Display for Stats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "peak {}, total {}, {} allocations",
+            format_bytes(self.peak_bytes),
+            format_bytes(self.total_bytes),
+            self.allocations
+        )
+    }
+}
+
+impl FromStr for Stats {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("invalid allocation stats \"{s}\"");
+
+        let fields = s.split(", ").collect::<Vec<_>>();
+        let [peak, total, allocations] = fields[..] else {
+            return Err(invalid());
+        };
+
+        Ok(Self {
+            peak_bytes: peak
+                .strip_prefix("peak ")
+                .and_then(parse_bytes)
+                .ok_or_else(invalid)?,
+            total_bytes: total
+                .strip_prefix("total ")
+                .and_then(parse_bytes)
+                .ok_or_else(invalid)?,
+            allocations: allocations
+                .strip_suffix(" allocations")
+                .and_then(|x| x.parse().ok())
+                .ok_or_else(invalid)?,
+        })
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_bytes, parse_bytes, Stats};
+
+    #[test]
+    fn formats_bytes() {
+        assert_eq!(format_bytes(0), "0 B");
+        assert_eq!(format_bytes(1023), "1023 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 MiB");
+    }
+
+    #[test]
+    fn parses_bytes() {
+        assert_eq!(parse_bytes("1023 B"), Some(1023));
+        assert_eq!(parse_bytes("1.5 KiB"), Some(1536));
+        assert_eq!(parse_bytes("3.0 MiB"), Some(3 * 1024 * 1024));
+        assert_eq!(parse_bytes("3.0 XB"), None);
+    }
+
+    #[test]
+    fn round_trips_stats() {
+        let stats = Stats {
+            peak_bytes: 512,
+            total_bytes: 1536,
+            allocations: 12,
+        };
+
+        assert_eq!(
+            stats.to_string(),
+            "peak 512 B, total 1.5 KiB, 12 allocations"
+        );
+        assert_eq!(stats.to_string().parse::<Stats>().unwrap(), stats);
+        assert!("peak 512 B".parse::<Stats>().is_err());
+    }
+}
@@ -1,9 +1,13 @@
 /// Wrapper module around the "aoc-cli" command-line.
 use std::{
     fmt::Display,
+    fs,
+    path::{Path, PathBuf},
     process::{Command, Output, Stdio},
 };
 
+use crate::template::cache;
+use crate::template::input::{self, Source};
 use crate::Day;
 
 #[derive(Debug)]
@@ -35,62 +39,129 @@ pub fn check() -> Result<(), AocCommandError> {
     Ok(())
 }
 
-pub fn read(day: Day) -> Result<Output, AocCommandError> {
+/// Downloads the input and description of a day. If `input_set` is given, the input is saved to
+/// `data/inputs/<input_set>/NN.txt` instead, typically together with the `session_file` of the
+/// account the set belongs to. An input that didn't change is left untouched, so that its
+/// modification time and encrypted form stay the same.
+pub fn download(
+    day: Day,
+    input_set: Option<&str>,
+    session_file: Option<&str>,
+) -> Result<Output, AocCommandError> {
+    let input_path = get_input_path(day, input_set);
+    let download_path = format!("{input_path}.download");
     let puzzle_path = get_puzzle_path(day);
 
-    let args = build_args(
-        "read",
-        &[
-            "--description-only".into(),
-            "--puzzle-file".into(),
-            puzzle_path,
-        ],
-        day,
-    );
+    for path in [&input_path, &puzzle_path] {
+        if let Some(parent) = Path::new(path).parent() {
+            fs::create_dir_all(parent).map_err(|_| AocCommandError::IoError)?;
+        }
+    }
 
-    call_aoc_cli(&args)
+    let mut args = vec![
+        "--overwrite".into(),
+        "--input-file".into(),
+        download_path.clone(),
+        "--puzzle-file".into(),
+        puzzle_path.to_string(),
+    ];
+
+    if let Some(session_file) = session_file {
+        args.push("--session-file".into());
+        args.push(session_file.into());
+    }
+
+    let args = build_args("download", &args, day);
+
+    let output = call_aoc_cli(&args)?;
+    let input_message = match store_input(Path::new(&download_path), Path::new(&input_path)) {
+        Ok(Some(path)) => format!("🎄 Successfully wrote input to \"{}\".", path.display()),
+        Ok(None) => format!("🎄 Input \"{input_path}\" is unchanged."),
+        Err(e) => {
+            eprintln!("Failed to store input: {e}");
+            return Err(AocCommandError::IoError);
+        }
+    };
+
+    if let Err(e) = cache::record_download(day) {
+        eprintln!("Failed to update the puzzle cache: {e}");
+    }
+
+    println!("---");
+    println!("{input_message}");
+    println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
+    Ok(output)
 }
 
-pub fn download(day: Day) -> Result<Output, AocCommandError> {
-    let input_path = get_input_path(day);
+/// Moves a downloaded input into place, unless it is identical to the stored input. Returns the
+/// path the input was stored at, or `None` if it was unchanged.
+fn store_input(download_path: &Path, input_path: &Path) -> Result<Option<PathBuf>, String> {
+    let downloaded = fs::read_to_string(download_path).map_err(|e| e.to_string())?;
+
+    if input::read_plaintext(input_path).is_ok_and(|existing| existing == downloaded) {
+        fs::remove_file(download_path).map_err(|e| e.to_string())?;
+        return Ok(None);
+    }
+
+    fs::rename(download_path, input_path).map_err(|e| e.to_string())?;
+    input::store_downloaded(input_path).map(Some)
+}
+
+/// Downloads only the description of a day, e.g. to add part two once part one was solved.
+pub fn download_description(day: Day) -> Result<Output, AocCommandError> {
     let puzzle_path = get_puzzle_path(day);
 
+    if let Some(parent) = Path::new(&puzzle_path).parent() {
+        fs::create_dir_all(parent).map_err(|_| AocCommandError::IoError)?;
+    }
+
     let args = build_args(
         "download",
         &[
             "--overwrite".into(),
-            "--input-file".into(),
-            input_path.to_string(),
+            "--puzzle-only".into(),
             "--puzzle-file".into(),
-            puzzle_path.to_string(),
+            puzzle_path,
         ],
         day,
     );
 
-    let output = call_aoc_cli(&args)?;
-    println!("---");
-    println!("🎄 Successfully wrote input to \"{}\".", &input_path);
-    println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
-    Ok(output)
+    call_aoc_cli(&args)
 }
 
+/// Submits an answer. The response of the server is printed and returned in the output.
 pub fn submit(day: Day, part: u8, result: &str) -> Result<Output, AocCommandError> {
     // workaround: the argument order is inverted for submit.
     let mut args = build_args("submit", &[], day);
     args.push(part.to_string());
     args.push(result.to_string());
-    call_aoc_cli(&args)
+
+    let output = Command::new("aoc")
+        .args(&args)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit())
+        .output()
+        .map_err(|_| AocCommandError::CommandNotCallable)?;
+
+    print!("{}", String::from_utf8_lossy(&output.stdout));
+
+    if output.status.success() {
+        Ok(output)
+    } else {
+        Err(AocCommandError::BadExitStatus(output))
+    }
 }
 
-fn get_input_path(day: Day) -> String {
-    format!("data/inputs/{day}.txt")
+fn get_input_path(day: Day, input_set: Option<&str>) -> String {
+    let source = input_set.map_or(Source::Default, |name| Source::Set(name.into()));
+    source.path(day).unwrap().to_string_lossy().to_string()
 }
 
-fn get_puzzle_path(day: Day) -> String {
+pub fn get_puzzle_path(day: Day) -> String {
     format!("data/puzzles/{day}.md")
 }
 
-fn get_year() -> Option<u16> {
+pub fn get_year() -> Option<u16> {
     match std::env::var("AOC_YEAR") {
         Ok(x) => x.parse().ok().or(None),
         Err(_) => None,
@@ -0,0 +1,153 @@
+/// Offline cache of the puzzle descriptions in `data/puzzles/`. The cache file
+/// `data/puzzles/cache.txt` records how many parts each saved description contains and when it was
+/// fetched, so that commands can tell whether a description is stale without hitting the network.
+use std::collections::BTreeMap;
+use std::time::{SystemTime, UNIX_EPOCH};
+use std::{fs, io};
+
+use crate::template::{aoc_cli, puzzle};
+use crate::Day;
+
+pub const CACHE_FILE: &str = "data/puzzles/cache.txt";
+
+const HEADER: &str = "# day  parts  fetched at (unix time)";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Entry {
+    /// Number of parts in the saved description, `2` once part one was solved.
+    pub parts: u8,
+    /// Seconds since the unix epoch.
+    pub fetched_at: u64,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Cache {
+    pub entries: BTreeMap<Day, Entry>,
+}
+
+impl Cache {
+    /// Reads the cache file. A missing file is treated as an empty cache.
+    pub fn load() -> io::Result<Self> {
+        match fs::read_to_string(CACHE_FILE) {
+            Ok(cache) => Ok(Self::parse(&cache)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    fn parse(cache: &str) -> Self {
+        let entries = cache
+            .lines()
+            .filter(|line| !line.starts_with('#'))
+            .filter_map(|line| {
+                let mut fields = line.split_whitespace();
+                let day = fields.next()?.parse().ok()?;
+                let parts = fields.next()?.parse().ok()?;
+                let fetched_at = fields.next()?.parse().ok()?;
+                Some((day, Entry { parts, fetched_at }))
+            })
+            .collect();
+
+        Self { entries }
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        fs::write(CACHE_FILE, self.to_string())
+    }
+
+    /// Records the description of `day` that was just saved to `data/puzzles/NN.md`.
+    pub fn record(&mut self, day: Day, description: &str) -> Entry {
+        let fetched_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |x| x.as_secs());
+
+        let entry = Entry {
+            parts: u8::try_from(puzzle::split_parts(description).len()).unwrap_or(u8::MAX),
+            fetched_at,
+        };
+
+        self.entries.insert(day, entry);
+        entry
+    }
+
+    /// Whether the saved description of `day` contains `part`. Descriptions that were saved
+    /// before the cache file existed are checked directly.
+    pub fn contains(&self, day: Day, part: u8) -> bool {
+        match self.entries.get(&day) {
+            Some(entry) => entry.parts >= part,
+            None => fs::read_to_string(aoc_cli::get_puzzle_path(day))
+                .is_ok_and(|description| puzzle::split_parts(&description).len() >= part.into()),
+        }
+    }
+}
+
+impl std::fmt::Display for Cache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{HEADER}")?;
+        for (day, entry) in &self.entries {
+            writeln!(f, "{day}  {}  {}", entry.parts, entry.fetched_at)?;
+        }
+        Ok(())
+    }
+}
+
+/// Records the saved description of `day` in the cache file.
+pub fn record_download(day: Day) -> Result<Entry, String> {
+    let description =
+        fs::read_to_string(aoc_cli::get_puzzle_path(day)).map_err(|e| e.to_string())?;
+    let mut cache = Cache::load().map_err(|e| e.to_string())?;
+    let entry = cache.record(day, &description);
+    cache.save().map_err(|e| e.to_string())?;
+    Ok(entry)
+}
+
+/// Fetches the description of `day` again, leaving the input alone, e.g. to add part two to the
+/// cache once part one was solved.
+pub fn refresh_description(day: Day) -> Result<Entry, String> {
+    aoc_cli::download_description(day).map_err(|e| e.to_string())?;
+    record_download(day)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Cache, Entry};
+    use crate::day;
+
+    #[test]
+    fn round_trips_cache() {
+        let mut cache = Cache::default();
+        cache.entries.insert(
+            day!(1),
+            Entry {
+                parts: 2,
+                fetched_at: 1_701_406_800,
+            },
+        );
+        cache.entries.insert(
+            day!(12),
+            Entry {
+                parts: 1,
+                fetched_at: 1_702_357_200,
+            },
+        );
+
+        assert_eq!(Cache::parse(&cache.to_string()), cache);
+        assert!(cache.to_string().contains("\n01  2  1701406800\n"));
+        assert!(cache.contains(day!(1), 2));
+        assert!(!cache.contains(day!(12), 2));
+    }
+
+    #[test]
+    fn counts_parts() {
+        let mut cache = Cache::default();
+        let entry = cache.record(
+            day!(3),
+            "\\--- Day 3: Gear Ratios ---\n\n\\--- Part Two ---\n",
+        );
+        assert_eq!(entry.parts, 2);
+
+        let entry = cache.record(day!(3), "\\--- Day 3: Gear Ratios ---\n");
+        assert_eq!(entry.parts, 1);
+        assert_eq!(cache.entries[&day!(3)], entry);
+    }
+}
@@ -0,0 +1,187 @@
+/// Named example cases, stored in `data/examples/NN/`. Each case consists of an input file
+/// `<name>.txt` and a sidecar file `<name>.case` that lists the expected answers and optional
+/// parameters of the case, one `key = value` pair per line:
+///
+/// ```text
+/// # lines starting with `#` are comments.
+/// part_one = 16
+/// steps = 6
+/// ```
+///
+/// Use [`crate::example_tests`] to generate a test for each case.
+use std::fmt::Display;
+use std::str::FromStr;
+use std::{env, fs};
+
+use crate::template::params;
+use crate::Day;
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ExampleCase {
+    pub name: String,
+    pub input: String,
+    pub part_one: Option<String>,
+    pub part_two: Option<String>,
+    /// All other `key = value` pairs of the sidecar file. These override the parameters declared
+    /// in the `solution!` macro while the case is checked.
+    pub params: Vec<(String, String)>,
+}
+
+impl ExampleCase {
+    /// Reads the case `name` of `day`. Panics if the input or the sidecar file can't be read or
+    /// parsed, as this is meant to be used from tests.
+    #[must_use]
+    pub fn load(day: Day, name: &str) -> Self {
+        let dir = env::current_dir()
+            .unwrap()
+            .join("data")
+            .join("examples")
+            .join(day.to_string());
+
+        let input = fs::read_to_string(dir.join(format!("{name}.txt")))
+            .unwrap_or_else(|e| panic!("could not open input of example \"{name}\": {e}"));
+        let sidecar = fs::read_to_string(dir.join(format!("{name}.case")))
+            .unwrap_or_else(|e| panic!("could not open sidecar of example \"{name}\": {e}"));
+
+        let mut case = Self::parse(name, &sidecar).unwrap_or_else(|e| panic!("{e}"));
+        case.input = input;
+        case
+    }
+
+    pub(crate) fn parse(name: &str, sidecar: &str) -> Result<Self, String> {
+        let mut case = Self {
+            name: name.to_string(),
+            ..Self::default()
+        };
+
+        for (index, line) in sidecar.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let Some((key, value)) = line.split_once('=') else {
+                return Err(format!(
+                    "{name}.case:{}: expected `key = value`, found \"{line}\"",
+                    index + 1
+                ));
+            };
+
+            let (key, value) = (key.trim(), value.trim().to_string());
+            match key {
+                "part_one" => case.part_one = Some(value),
+                "part_two" => case.part_two = Some(value),
+                _ => case.params.push((key.to_string(), value)),
+            }
+        }
+
+        Ok(case)
+    }
+
+    /// Returns the parameter `name` of the case, e.g. the number of steps to simulate.
+    pub fn param<T: FromStr>(&self, name: &str) -> Option<T> {
+        self.params
+            .iter()
+            .find(|(key, _)| key == name)
+            .and_then(|(_, value)| value.parse().ok())
+    }
+
+    /// Runs `solve` on the case's input and compares the result to the expected answer of `part`,
+    /// if the case lists one. Returns whether the part was checked.
+    pub fn check<T: Display>(&self, part: u8, solve: impl Fn(&str) -> Option<T>) -> bool {
+        let expected = match part {
+            1 => &self.part_one,
+            _ => &self.part_two,
+        };
+
+        let Some(expected) = expected else {
+            return false;
+        };
+
+        let result =
+            params::with(&self.params, || solve(&self.input)).map(|result| result.to_string());
+        assert_eq!(
+            result.as_deref(),
+            Some(expected.as_str()),
+            "example \"{}\", part {part}",
+            self.name
+        );
+        true
+    }
+}
+
+/// Generates a `#[test]` for each named example case of the current day. Each test checks
+/// `part_one` and `part_two` against the answers listed in the case's sidecar file.
+///
+/// ```ignore
+/// #[cfg(test)]
+/// mod tests {
+///     use super::*;
+///
+///     advent_of_code::example_tests!(simple_loop, squeezed_loop);
+/// }
+/// ```
+#[macro_export]
+macro_rules! example_tests {
+    ($($case:ident),+ $(,)?) => {
+        $(
+            #[test]
+            fn $case() {
+                let case =
+                    advent_of_code::template::cases::ExampleCase::load(DAY, stringify!($case));
+                let checked_one = case.check(1, part_one);
+                let checked_two = case.check(2, part_two);
+                assert!(
+                    checked_one || checked_two,
+                    "example \"{}\" does not list an expected answer",
+                    stringify!($case)
+                );
+            }
+        )+
+    };
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::ExampleCase;
+
+    #[test]
+    fn parses_sidecar() {
+        let case = ExampleCase::parse("a", "# comment\n\npart_two = 16\n steps = 6 \n").unwrap();
+        assert_eq!(case.part_one, None);
+        assert_eq!(case.part_two.as_deref(), Some("16"));
+        assert_eq!(case.param::<usize>("steps"), Some(6));
+        assert_eq!(case.param::<usize>("other"), None);
+    }
+
+    #[test]
+    fn errors_on_invalid_lines() {
+        assert_eq!(
+            ExampleCase::parse("a", "part_one = 1\nsteps").unwrap_err(),
+            "a.case:2: expected `key = value`, found \"steps\""
+        );
+    }
+
+    #[test]
+    fn checks_answers() {
+        let case = ExampleCase {
+            input: "abc".into(),
+            part_one: Some("3".into()),
+            ..ExampleCase::default()
+        };
+
+        assert!(case.check(1, |input| Some(input.len())));
+        assert!(!case.check(2, |_| None::<u32>));
+    }
+
+    #[test]
+    #[should_panic(expected = "part 1")]
+    fn panics_on_wrong_answers() {
+        let case = ExampleCase {
+            part_one: Some("3".into()),
+            ..ExampleCase::default()
+        };
+
+        case.check(1, |_| Some(2));
+    }
+}
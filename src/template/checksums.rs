@@ -0,0 +1,145 @@
+/// A checksum manifest of the puzzle inputs, `data/inputs/checksums.sha256`, to detect accidental
+/// edits or truncation. The manifest uses the format of `sha256sum`, so it can also be checked with
+/// `cd data/inputs && sha256sum -c checksums.sha256` while the inputs are not encrypted.
+/// Checksums are always computed over the plaintext of an input.
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::path::{Component, Path};
+use std::{env, fs, io};
+
+use sha2::{Digest, Sha256};
+
+pub const INPUTS_DIR: &str = "data/inputs";
+pub const MANIFEST: &str = "data/inputs/checksums.sha256";
+
+pub fn sha256_hex(data: &[u8]) -> String {
+    Sha256::digest(data)
+        .iter()
+        .fold(String::with_capacity(64), |mut hex, byte| {
+            let _ = write!(hex, "{byte:02x}");
+            hex
+        })
+}
+
+/// Name of an input in the manifest: its path relative to `data/inputs`, e.g. `alice/01.txt`.
+/// Returns `None` for files outside of `data/inputs`.
+pub fn manifest_name(path: &Path) -> Option<String> {
+    let cwd = env::current_dir().ok()?;
+    let path = path.strip_prefix(&cwd).unwrap_or(path);
+    let relative = path.strip_prefix(INPUTS_DIR).ok()?;
+
+    let parts = relative
+        .components()
+        .map(|component| match component {
+            Component::Normal(part) => Some(part.to_string_lossy().to_string()),
+            _ => None,
+        })
+        .collect::<Option<Vec<_>>>()?;
+
+    Some(parts.join("/"))
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Manifest {
+    /// Checksums by manifest name.
+    pub entries: BTreeMap<String, String>,
+}
+
+impl Manifest {
+    /// Reads the manifest. A missing manifest is treated as empty.
+    pub fn load() -> io::Result<Self> {
+        match fs::read_to_string(MANIFEST) {
+            Ok(manifest) => Ok(Self::parse(&manifest)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    fn parse(manifest: &str) -> Self {
+        let entries = manifest
+            .lines()
+            .filter_map(|line| {
+                let (checksum, name) = line.split_once(char::is_whitespace)?;
+                // `sha256sum` marks binary mode with `*`.
+                let name = name.trim_start().trim_start_matches('*');
+                Some((name.to_string(), checksum.to_ascii_lowercase()))
+            })
+            .collect();
+
+        Self { entries }
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        fs::write(MANIFEST, self.to_string())
+    }
+
+    /// Checks `contents` against the checksum recorded for `name`. Inputs without a recorded
+    /// checksum are accepted.
+    pub fn verify(&self, name: &str, contents: &[u8]) -> Result<(), String> {
+        match self.entries.get(name) {
+            Some(expected) if *expected != sha256_hex(contents) => Err(format!(
+                "\"{name}\" does not match its checksum in \"{MANIFEST}\". The input was edited or truncated; download it again, or run `cargo verify --update` if the change was intended."
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    pub fn record(&mut self, name: &str, contents: &[u8]) {
+        self.entries.insert(name.to_string(), sha256_hex(contents));
+    }
+}
+
+impl std::fmt::Display for Manifest {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (name, checksum) in &self.entries {
+            writeln!(f, "{checksum}  {name}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{manifest_name, sha256_hex, Manifest};
+    use std::path::Path;
+
+    #[test]
+    fn hashes_contents() {
+        assert_eq!(
+            sha256_hex(b"abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+
+    #[test]
+    fn round_trips_manifest() {
+        let mut manifest = Manifest::default();
+        manifest.record("01.txt", b"abc");
+        manifest.record("alice/01.txt", b"def");
+
+        let parsed = Manifest::parse(&manifest.to_string());
+        assert_eq!(parsed, manifest);
+        assert!(manifest.to_string().starts_with(
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad  01.txt\n"
+        ));
+    }
+
+    #[test]
+    fn verifies_contents() {
+        let mut manifest = Manifest::default();
+        manifest.record("01.txt", b"abc");
+
+        assert!(manifest.verify("01.txt", b"abc").is_ok());
+        assert!(manifest.verify("01.txt", b"ab").is_err());
+        assert!(manifest.verify("02.txt", b"anything").is_ok());
+    }
+
+    #[test]
+    fn names_inputs() {
+        assert_eq!(
+            manifest_name(Path::new("data/inputs/alice/01.txt")).as_deref(),
+            Some("alice/01.txt")
+        );
+        assert_eq!(manifest_name(Path::new("data/examples/01.txt")), None);
+    }
+}
@@ -1,13 +1,42 @@
-use std::io;
+use std::{io, process};
 
 use crate::template::{
+    config::Config,
+    environment::Environment,
     readme_benchmarks::{self, Timings},
+    readme_stars,
+    reports::{self, Format, Report},
     ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
 };
 use crate::{all_days, Day};
 
-pub fn handle(is_release: bool, is_timed: bool) {
+/// Options of the `all` command.
+#[derive(Default)]
+pub struct Options {
+    pub release: bool,
+    pub time: bool,
+    /// Build with the counting allocator of the `alloc_stats` feature.
+    pub memory: bool,
+    /// Pin the thread that runs the parts to this core.
+    pub pin: Option<usize>,
+}
+
+pub fn handle(options: &Options) {
     let mut timings: Vec<Timings> = vec![];
+    let mut failed = false;
+
+    // captured up front, so that the load of the benchmark itself does not trigger a warning.
+    let environment = options.time.then(|| {
+        let mut environment = Environment::capture(options.release, options.pin);
+        if options.memory {
+            let warning = "Timings include the overhead of the counting allocator of `--memory`.";
+            environment.warnings.push(warning.into());
+        }
+        for warning in &environment.warnings {
+            eprintln!("⚠️ {warning}");
+        }
+        environment
+    });
 
     all_days().for_each(|day| {
         if day > 1 {
@@ -17,7 +46,14 @@ pub fn handle(is_release: bool, is_timed: bool) {
         println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
         println!("------");
 
-        let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+        let output = match child_commands::run_solution(day, options) {
+            Ok(output) => output,
+            Err(e) => {
+                eprintln!("Failed to run day {day}: {e}");
+                failed = true;
+                return;
+            }
+        };
 
         if output.is_empty() {
             println!("Not solved.");
@@ -27,20 +63,104 @@ pub fn handle(is_release: bool, is_timed: bool) {
         }
     });
 
-    if is_timed {
+    if options.time {
         let total_millis = timings.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64;
 
         println!("\n{ANSI_BOLD}Total:{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}");
 
-        if is_release {
-            match readme_benchmarks::update(timings, total_millis) {
-                Ok(()) => println!("Successfully updated README with benchmarks."),
-                Err(_) => {
-                    eprintln!("Failed to update readme with benchmarks.");
-                }
+        if options.release {
+            failed |= !write_reports(&timings, total_millis, environment.as_ref(), options.memory);
+        }
+    }
+
+    if failed {
+        process::exit(1);
+    }
+}
+
+/// Saves the timings, appends them to the history and writes all configured reports. Returns
+/// whether all reports were written.
+///
+/// Timings measured with the counting allocator are labelled by a warning of the environment. They
+/// are not saved for the progress table and the dashboard, which can't show that label.
+fn write_reports(
+    timings: &[Timings],
+    total_millis: f64,
+    environment: Option<&Environment>,
+    instrumented: bool,
+) -> bool {
+    let mut success = true;
+
+    if instrumented {
+        println!(
+            "Timings measured with --memory are not saved to \"{}\".",
+            readme_benchmarks::TIMINGS_FILE
+        );
+    } else if let Err(e) = readme_benchmarks::save_timings(timings) {
+        eprintln!("Failed to save timings: {e}");
+        success = false;
+    }
+
+    let config = match Config::load() {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("Failed to write reports: {e}");
+            return false;
+        }
+    };
+
+    let combine = config.readme.combine_stars_and_timings;
+    let report = Report {
+        timings,
+        total_millis,
+        environment,
+    };
+
+    if let Err(e) = reports::append_history(&report) {
+        eprintln!(
+            "Failed to append timings to \"{}\": {e}",
+            reports::HISTORY_FILE
+        );
+        success = false;
+    }
+
+    for target in config.report_targets() {
+        // the timings are part of the progress table instead.
+        if combine && target.format == Format::Readme {
+            continue;
+        }
+
+        match target.write(&report) {
+            Ok(()) if target.format == Format::Readme => {
+                println!("Successfully updated {} with benchmarks.", target.path());
+            }
+            Ok(()) => println!(
+                "Successfully wrote {} report to \"{}\".",
+                target.format,
+                target.path()
+            ),
+            Err(e) => {
+                eprintln!(
+                    "Failed to write {} report to \"{}\": {e}",
+                    target.format,
+                    target.path()
+                );
+                success = false;
             }
         }
     }
+
+    if combine && !instrumented {
+        match readme_stars::refresh() {
+            Ok(()) => println!("Successfully updated README with ⭐️ progress and benchmarks."),
+            Err(e) => {
+                eprintln!("Failed to update readme with ⭐️ progress: {e}");
+                success = false;
+            }
+        }
+    }
+
+    success
 }
 
 #[derive(Debug)]
@@ -56,6 +176,16 @@ impl From<std::io::Error> for Error {
     }
 }
 
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::BrokenPipe => write!(f, "could not read the output"),
+            Error::Parser(e) => write!(f, "{e}"),
+            Error::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
 #[must_use]
 pub fn get_path_for_bin(day: Day) -> String {
     format!("./src/bin/{day}.rs")
@@ -64,7 +194,9 @@ pub fn get_path_for_bin(day: Day) -> String {
 /// All solutions live in isolated binaries.
 /// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
 mod child_commands {
-    use super::{get_path_for_bin, Error};
+    use super::{get_path_for_bin, Error, Options};
+    use crate::template::readme_benchmarks::parse_duration;
+    use crate::template::runner;
     use crate::Day;
     use std::{
         io::{BufRead, BufReader},
@@ -74,25 +206,37 @@ mod child_commands {
     };
 
     /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
+    pub fn run_solution(day: Day, options: &Options) -> Result<Vec<String>, Error> {
         // skip command invocation for days that have not been scaffolded yet.
         if !Path::new(&get_path_for_bin(day)).exists() {
             return Ok(vec![]);
         }
 
         let day_padded = day.to_string();
+        let pin = options.pin.map(|x| x.to_string());
         let mut args = vec!["run", "--quiet", "--bin", &day_padded];
 
-        if is_release {
+        if options.release {
             args.push("--release");
         }
 
-        if is_timed {
+        if options.memory {
+            args.push("--features");
+            args.push("alloc_stats");
+        }
+
+        args.push("--");
+
+        if options.time {
             // mirror `--time` flag to child invocations.
-            args.push("--");
             args.push("--time");
         }
 
+        if let Some(pin) = &pin {
+            args.push("--pin");
+            args.push(pin);
+        }
+
         // spawn child command with piped stdout/stderr.
         // forward output to stdout/stderr while grabbing stdout lines.
 
@@ -131,8 +275,13 @@ mod child_commands {
             part_1: None,
             part_2: None,
             total_nanos: 0_f64,
+            part_1_memory: None,
+            part_2_memory: None,
         };
 
+        [timings.part_1_memory, timings.part_2_memory] =
+            runner::parse_alloc_stats(&output.join("\n"));
+
         output
             .iter()
             .filter_map(|l| {
@@ -161,12 +310,7 @@ mod child_commands {
         timings
     }
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
-    }
-
     fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
         let str_timing = line
             .split(" samples)")
             .next()?
@@ -176,14 +320,7 @@ mod child_commands {
             .next()?
             .trim();
 
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
+        Some((str_timing, parse_duration(str_timing)?))
     }
 
     /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
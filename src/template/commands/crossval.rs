@@ -0,0 +1,221 @@
+use std::fmt::Display;
+use std::path::Path;
+use std::process::{self, Command};
+use std::{fs, io};
+
+use crate::template::cases::ExampleCase;
+use crate::template::commands::all::get_path_for_bin;
+use crate::template::encryption;
+use crate::template::input::{self, Source};
+use crate::template::{runner, ANSI_BOLD, ANSI_RESET};
+use crate::{all_days, Day};
+
+/// Outcome of running one day against one input set.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Outcome {
+    /// All expected answers matched.
+    Pass,
+    /// At least one answer differed from the expected answer.
+    Fail(Vec<String>),
+    /// The solution ran, but the input set does not list expected answers.
+    Unchecked,
+    /// The input set has no input for the day.
+    Missing,
+    /// The solution binary exited with an error.
+    Error,
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Outcome::Pass => write!(f, "✓"),
+            Outcome::Fail(_) => write!(f, "✗"),
+            Outcome::Unchecked => write!(f, "?"),
+            Outcome::Missing => write!(f, "-"),
+            Outcome::Error => write!(f, "!"),
+        }
+    }
+}
+
+pub fn handle(is_release: bool) {
+    let sets = match input::input_sets() {
+        Ok(sets) => sets,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => vec![],
+        Err(e) => {
+            eprintln!("Failed to list input sets: {e}");
+            process::exit(1);
+        }
+    };
+
+    // the default input `data/inputs/NN.txt` is checked alongside the input sets.
+    let sources = std::iter::once(Source::Default)
+        .chain(sets.into_iter().map(Source::Set))
+        .collect::<Vec<_>>();
+
+    let days = all_days()
+        .filter(|day| Path::new(&get_path_for_bin(*day)).exists())
+        .collect::<Vec<_>>();
+
+    let mut rows = vec![];
+
+    for day in days {
+        let outcomes = sources
+            .iter()
+            .map(|source| check(day, source, is_release))
+            .collect::<Vec<_>>();
+
+        // skip days that none of the input sets has an input for.
+        if outcomes.iter().any(|outcome| *outcome != Outcome::Missing) {
+            rows.push((day, outcomes));
+        }
+    }
+    eprint!("\r{:60}\r", "");
+
+    print_matrix(&sources, &rows);
+
+    let failures = rows
+        .iter()
+        .flat_map(|(day, outcomes)| {
+            outcomes
+                .iter()
+                .zip(&sources)
+                .map(move |(outcome, source)| (day, source, outcome))
+        })
+        .filter(|(_, _, outcome)| matches!(outcome, Outcome::Fail(_) | Outcome::Error))
+        .collect::<Vec<_>>();
+
+    if failures.is_empty() {
+        return;
+    }
+
+    println!();
+    for (day, source, outcome) in &failures {
+        match outcome {
+            Outcome::Fail(mismatches) => {
+                for mismatch in mismatches {
+                    println!("Day {day}, {source}: {mismatch}");
+                }
+            }
+            _ => println!("Day {day}, {source}: the solution exited with an error."),
+        }
+    }
+
+    process::exit(1);
+}
+
+fn print_matrix(sources: &[Source], rows: &[(Day, Vec<Outcome>)]) {
+    let labels = sources.iter().map(column_label).collect::<Vec<_>>();
+    let widths = labels
+        .iter()
+        .map(|label| label.len().max(1))
+        .collect::<Vec<_>>();
+
+    print!("{ANSI_BOLD}Day{ANSI_RESET}");
+    for (label, width) in labels.iter().zip(&widths) {
+        print!("  {ANSI_BOLD}{label:width$}{ANSI_RESET}");
+    }
+    println!();
+
+    for (day, outcomes) in rows {
+        print!("{day} ");
+        for (outcome, width) in outcomes.iter().zip(&widths) {
+            print!("  {:width$}", outcome.to_string());
+        }
+        println!();
+    }
+
+    println!();
+    println!("✓ passed  ✗ failed  ? no expected answers  - no input  ! error");
+}
+
+fn column_label(source: &Source) -> &str {
+    match source {
+        Source::Set(name) => name,
+        _ => "default",
+    }
+}
+
+/// Runs the solution of `day` against the default input or an input set and compares its answers
+/// to the ones listed in the sidecar file next to the input, `data/inputs/NN.case` or
+/// `data/inputs/<set>/NN.case`. Parameters listed in the sidecar file are passed to the solution.
+fn check(day: Day, source: &Source, is_release: bool) -> Outcome {
+    let Some(input_path) = source.path(day) else {
+        return Outcome::Missing;
+    };
+
+    if !input_path.exists() && !encryption::encrypted_path(&input_path).exists() {
+        return Outcome::Missing;
+    }
+
+    eprint!("\rRunning day {day} against {source}...          ");
+
+    let case = match read_case(&input_path.with_extension("case"), day) {
+        Ok(case) => case,
+        Err(e) => return Outcome::Fail(vec![e]),
+    };
+
+    let day_padded = day.to_string();
+    let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+
+    if is_release {
+        args.push("--release");
+    }
+
+    args.push("--");
+
+    if let Source::Set(set) = source {
+        args.extend(["--input-set", set]);
+    }
+
+    let params = case
+        .iter()
+        .flat_map(|case| &case.params)
+        .map(|(name, value)| format!("{name}={value}"))
+        .collect::<Vec<_>>();
+
+    for param in &params {
+        args.extend(["--param", param]);
+    }
+
+    let output = match Command::new("cargo").args(&args).output() {
+        Ok(output) if output.status.success() => output,
+        _ => return Outcome::Error,
+    };
+
+    let answers = runner::parse_answers(&String::from_utf8_lossy(&output.stdout));
+
+    let Some(case) = case else {
+        return Outcome::Unchecked;
+    };
+
+    let mismatches = [(1, &case.part_one), (2, &case.part_two)]
+        .into_iter()
+        .filter_map(|(part, expected)| {
+            let expected = expected.as_ref()?;
+            let actual = answers[part - 1].as_deref();
+            (actual != Some(expected.as_str())).then(|| {
+                format!(
+                    "part {part} expected {expected}, got {}",
+                    actual.unwrap_or("no answer")
+                )
+            })
+        })
+        .collect::<Vec<_>>();
+
+    if !mismatches.is_empty() {
+        Outcome::Fail(mismatches)
+    } else if case.part_one.is_some() || case.part_two.is_some() {
+        Outcome::Pass
+    } else {
+        Outcome::Unchecked
+    }
+}
+
+/// Reads the expected answers of an input set, `None` if it does not list any for the day.
+fn read_case(path: &Path, day: Day) -> Result<Option<ExampleCase>, String> {
+    match fs::read_to_string(path) {
+        Ok(sidecar) => ExampleCase::parse(&day.to_string(), &sidecar).map(Some),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(format!("could not read \"{}\": {e}", path.display())),
+    }
+}
@@ -0,0 +1,548 @@
+/// Interactive overview of all 25 days: which days are scaffolded, which parts are solved, whether
+/// the answers are verified and how long the last run took. Solutions are run, tested, benched and
+/// submitted with single key presses.
+use std::fmt::Write as _;
+use std::fs;
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::Path;
+use std::process::{self, Command, Stdio};
+use std::sync::mpsc::Receiver;
+
+use crate::template::cases::ExampleCase;
+use crate::template::commands::all::get_path_for_bin;
+use crate::template::input::Source;
+use crate::template::readme_benchmarks::{self, Timings};
+use crate::template::terminal::{
+    self, Screen, ANSI_ALTERNATE_SCREEN, ANSI_CLEAR, ANSI_HIDE_CURSOR, ANSI_MAIN_SCREEN,
+    ANSI_REVERSE, ANSI_SHOW_CURSOR,
+};
+use crate::template::{aoc_cli, encryption, markdown, runner, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use crate::{all_days, Day};
+
+const ANSWER_WIDTH: usize = 18;
+
+const HELP: &str =
+    "↑/↓ or j/k: select · r: run · t: test · b: bench · o: open puzzle · s/S: submit part 1/2 · q: quit";
+const PUZZLE_HELP: &str = "↑/↓ or j/k: scroll · space: page down · o/q: back";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Key {
+    Up,
+    Down,
+    PageDown,
+    Run,
+    Test,
+    Bench,
+    Open,
+    Submit(u8),
+    Quit,
+}
+
+/// Decodes key presses, including the escape sequences of the arrow keys.
+fn key_decoder() -> impl FnMut(u8) -> Option<Key> + Send + 'static {
+    let mut escape = 0;
+
+    move |byte| match (escape, byte) {
+        (_, 0x1b) => {
+            escape = 1;
+            None
+        }
+        (1, b'[') => {
+            escape = 2;
+            None
+        }
+        (2, b'A') => {
+            escape = 0;
+            Some(Key::Up)
+        }
+        (2, b'B') => {
+            escape = 0;
+            Some(Key::Down)
+        }
+        (2, _) => {
+            escape = 0;
+            None
+        }
+        (_, byte) => {
+            escape = 0;
+            match byte {
+                b'k' => Some(Key::Up),
+                b'j' => Some(Key::Down),
+                b' ' => Some(Key::PageDown),
+                b'r' => Some(Key::Run),
+                b't' => Some(Key::Test),
+                b'b' => Some(Key::Bench),
+                b'o' => Some(Key::Open),
+                b's' => Some(Key::Submit(1)),
+                b'S' => Some(Key::Submit(2)),
+                b'q' | terminal::CTRL_C => Some(Key::Quit),
+                _ => None,
+            }
+        }
+    }
+}
+
+/// What the dashboard knows about a day.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct DayState {
+    scaffolded: bool,
+    has_input: bool,
+    /// Known correct answers, from the sidecar file `data/inputs/NN.case`.
+    expected: [Option<String>; 2],
+    /// Answers of the last run.
+    answers: [Option<String>; 2],
+    /// Execution times of the last run, initially those of the last benchmark, see
+    /// [`readme_benchmarks::TIMINGS_FILE`].
+    timings: [Option<String>; 2],
+    /// Outcome of the last test run.
+    tests: Option<bool>,
+}
+
+impl DayState {
+    fn load(day: Day, timings: Option<&Timings>) -> Self {
+        let input_path = Source::Default.path(day).expect("default input has a path");
+        let expected = match fs::read_to_string(input_path.with_extension("case")) {
+            Ok(sidecar) => ExampleCase::parse(&day.to_string(), &sidecar)
+                .map(|case| [case.part_one, case.part_two])
+                .unwrap_or_default(),
+            Err(_) => [None, None],
+        };
+
+        Self {
+            scaffolded: Path::new(&get_path_for_bin(day)).exists(),
+            has_input: input_path.exists() || encryption::encrypted_path(&input_path).exists(),
+            expected,
+            timings: timings.map_or_else(Default::default, |timings| {
+                [timings.part_1.clone(), timings.part_2.clone()]
+            }),
+            ..Self::default()
+        }
+    }
+
+    fn status(&self) -> &'static str {
+        match (self.scaffolded, self.has_input) {
+            (false, _) => "-",
+            (true, false) => "no input",
+            (true, true) => "ready",
+        }
+    }
+
+    /// The answer of a part, or the known answer if the solution hasn't run yet, with a marker for
+    /// whether it matches the known answer.
+    fn answer(&self, index: usize) -> String {
+        let answer = match (&self.answers[index], &self.expected[index]) {
+            (Some(answer), Some(expected)) if answer == expected => format!("{answer} ✓"),
+            (Some(answer), Some(_)) => format!("{answer} ✗"),
+            (Some(answer), None) => answer.clone(),
+            (None, Some(expected)) => format!("{ANSI_ITALIC}{expected}{ANSI_RESET}"),
+            (None, None) => "-".into(),
+        };
+
+        // multi-line answers don't fit into the table.
+        if answer.contains('\n') {
+            "▼".into()
+        } else {
+            answer
+        }
+    }
+}
+
+struct Dashboard {
+    days: Vec<(Day, DayState)>,
+    selected: usize,
+    message: String,
+}
+
+impl Dashboard {
+    fn load() -> Self {
+        // the dashboard works without the timings of a benchmark, they only fill the time column.
+        let timings = readme_benchmarks::load_timings().unwrap_or_default();
+
+        Self {
+            days: all_days()
+                .map(|day| {
+                    let timings = timings.iter().find(|timings| timings.day == day);
+                    (day, DayState::load(day, timings))
+                })
+                .collect(),
+            selected: 0,
+            message: String::new(),
+        }
+    }
+
+    fn render(&self) -> String {
+        let mut screen = format!("{ANSI_BOLD}Advent of Code");
+        if let Some(year) = aoc_cli::get_year() {
+            let _ = write!(screen, " {year}");
+        }
+        let _ = writeln!(screen, "{ANSI_RESET}\n");
+
+        let _ = writeln!(
+            screen,
+            "{ANSI_BOLD}Day  {:10}{:w$}  {:w$}  {:20}Tests{ANSI_RESET}",
+            "Status",
+            "Part 1",
+            "Part 2",
+            "Time",
+            w = ANSWER_WIDTH
+        );
+
+        for (index, (day, state)) in self.days.iter().enumerate() {
+            let time = match &state.timings {
+                [Some(one), Some(two)] => format!("{one} / {two}"),
+                [Some(one), None] => one.clone(),
+                _ => "-".into(),
+            };
+            let tests = match state.tests {
+                Some(true) => "✓",
+                Some(false) => "✗",
+                None => "-",
+            };
+
+            let row = format!(
+                "{day}   {:10}{}  {}  {:20}{tests}",
+                state.status(),
+                pad(&state.answer(0), ANSWER_WIDTH),
+                pad(&state.answer(1), ANSWER_WIDTH),
+                time,
+            );
+
+            if index == self.selected {
+                let _ = writeln!(screen, "{ANSI_REVERSE}{row}{ANSI_RESET}");
+            } else {
+                let _ = writeln!(screen, "{row}");
+            }
+        }
+
+        let _ = write!(
+            screen,
+            "\n{}\n{ANSI_ITALIC}{HELP}{ANSI_RESET}\n",
+            self.message
+        );
+        screen
+    }
+
+    fn selected(&mut self) -> (Day, &mut DayState) {
+        let (day, state) = &mut self.days[self.selected];
+        (*day, state)
+    }
+}
+
+/// Pads `s` to `width` visible characters, ignoring ANSI sequences and truncating long answers.
+fn pad(s: &str, width: usize) -> String {
+    let visible = runner::strip_ansi(s);
+    let len = visible.chars().count();
+
+    if len > width {
+        let truncated = visible.chars().take(width - 1).collect::<String>();
+        format!("{truncated}…")
+    } else {
+        format!("{s}{}", " ".repeat(width - len))
+    }
+}
+
+pub fn handle() {
+    // the screen is restored when it is dropped at the end of the closure, also if `run` fails.
+    let result = Screen::enter(io::stdout()).and_then(|screen| {
+        let keys = screen
+            .saved_mode
+            .as_ref()
+            .and_then(|_| terminal::spawn_key_reader(key_decoder()));
+
+        match keys {
+            Some(keys) => run(&keys).map(|()| true),
+            None => Ok(false),
+        }
+    });
+
+    match result {
+        Ok(true) => {}
+        Ok(false) => {
+            eprintln!("The dashboard needs an interactive terminal.");
+            process::exit(1);
+        }
+        Err(e) => {
+            eprintln!("Dashboard failed: {e}");
+            process::exit(1);
+        }
+    }
+}
+
+fn run(keys: &Receiver<Key>) -> io::Result<()> {
+    let mut stdout = io::stdout();
+    let mut dashboard = Dashboard::load();
+
+    loop {
+        write!(stdout, "{ANSI_CLEAR}{}", dashboard.render())?;
+        stdout.flush()?;
+
+        let Ok(key) = keys.recv() else { break };
+        dashboard.message.clear();
+
+        match key {
+            Key::Quit => break,
+            Key::Up => dashboard.selected = dashboard.selected.saturating_sub(1),
+            Key::Down => {
+                dashboard.selected = (dashboard.selected + 1).min(dashboard.days.len() - 1)
+            }
+            Key::PageDown => {}
+            Key::Open => {
+                let (day, _) = dashboard.selected();
+                match fs::read_to_string(format!("data/puzzles/{day}.md")) {
+                    Ok(puzzle) => show_puzzle(day, &puzzle, keys)?,
+                    Err(_) => {
+                        dashboard.message = format!(
+                            "No puzzle description for day {day}. Run `cargo download {day}`."
+                        );
+                    }
+                }
+            }
+            Key::Run | Key::Test | Key::Bench | Key::Submit(_) => {
+                let (day, state) = dashboard.selected();
+                if !state.scaffolded {
+                    dashboard.message =
+                        format!("Day {day} is not scaffolded. Run `cargo scaffold {day}`.");
+                    continue;
+                }
+
+                write!(stdout, "{ANSI_CLEAR}{}", dashboard.render())?;
+                if let Key::Submit(part) = key {
+                    dashboard.message =
+                        submit(day, part, &mut dashboard.days[dashboard.selected].1, keys)?;
+                } else {
+                    writeln!(stdout, "{ANSI_ITALIC}Working on day {day}...{ANSI_RESET}")?;
+                    stdout.flush()?;
+                    let (_, state) = dashboard.selected();
+                    dashboard.message = execute(day, key, state);
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Runs, tests or benches a day in the background and records the outcome.
+fn execute(day: Day, key: Key, state: &mut DayState) -> String {
+    let day_padded = day.to_string();
+    let args = match key {
+        Key::Test => vec!["test", "--quiet", "--bin", &day_padded],
+        Key::Bench => vec![
+            "run",
+            "--quiet",
+            "--release",
+            "--bin",
+            &day_padded,
+            "--",
+            "--time",
+        ],
+        _ => vec!["run", "--quiet", "--release", "--bin", &day_padded],
+    };
+
+    let output = match Command::new("cargo")
+        .args(&args)
+        .stdin(Stdio::null())
+        .output()
+    {
+        Ok(output) => output,
+        Err(e) => return format!("Failed to run cargo: {e}"),
+    };
+
+    if key == Key::Test {
+        state.tests = Some(output.status.success());
+        return if output.status.success() {
+            format!("Tests of day {day} passed.")
+        } else {
+            format!("Tests of day {day} failed. Run `cargo test --bin {day}` for details.")
+        };
+    }
+
+    if !output.status.success() {
+        return format!("Day {day} failed. Run `cargo solve {day}` for details.");
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    state.answers = runner::parse_answers(&stdout);
+    state.timings = runner::parse_timings(&stdout);
+
+    if key == Key::Bench {
+        format!("Benched day {day}.")
+    } else {
+        format!("Solved day {day}.")
+    }
+}
+
+/// Submits a part on the main screen, so that the response of aoc-cli can be read, then waits for
+/// a key press before returning to the dashboard.
+fn submit(day: Day, part: u8, state: &mut DayState, keys: &Receiver<Key>) -> io::Result<String> {
+    let mut stdout = io::stdout();
+    write!(stdout, "{ANSI_SHOW_CURSOR}{ANSI_MAIN_SCREEN}")?;
+    writeln!(
+        stdout,
+        "{ANSI_BOLD}Submitting part {part} of day {day}{ANSI_RESET}"
+    )?;
+    stdout.flush()?;
+
+    let day_padded = day.to_string();
+    let part = part.to_string();
+    let mut cmd = Command::new("cargo")
+        .args([
+            "run",
+            "--quiet",
+            "--release",
+            "--bin",
+            &day_padded,
+            "--",
+            "--submit",
+            &part,
+        ])
+        .stdin(Stdio::inherit())
+        .stdout(Stdio::piped())
+        .spawn()?;
+
+    let mut output = String::new();
+    for line in BufReader::new(cmd.stdout.take().expect("stdout is piped")).lines() {
+        let line = line?;
+        writeln!(stdout, "{line}")?;
+        output.push_str(&line);
+        output.push('\n');
+    }
+
+    let status = cmd.wait()?;
+    state.answers = runner::parse_answers(&output);
+    state.timings = runner::parse_timings(&output);
+
+    writeln!(
+        stdout,
+        "\n{ANSI_ITALIC}Press any key to return to the dashboard.{ANSI_RESET}"
+    )?;
+    stdout.flush()?;
+    let _ = keys.recv();
+
+    write!(stdout, "{ANSI_ALTERNATE_SCREEN}{ANSI_HIDE_CURSOR}")?;
+
+    Ok(if status.success() {
+        format!("Submitted part {part} of day {day}.")
+    } else {
+        format!("Submitting part {part} of day {day} failed.")
+    })
+}
+
+/// Shows the rendered puzzle description, scrolled with the arrow keys.
+fn show_puzzle(day: Day, puzzle: &str, keys: &Receiver<Key>) -> io::Result<()> {
+    let mut stdout = io::stdout();
+    let (rows, columns) = terminal::size().unwrap_or((24, 80));
+    let puzzle = markdown::render(puzzle, columns);
+    let lines = puzzle.lines().collect::<Vec<_>>();
+    let height = rows.saturating_sub(3).max(1);
+    let mut offset = 0;
+
+    loop {
+        let end = (offset + height).min(lines.len());
+        write!(
+            stdout,
+            "{ANSI_CLEAR}{ANSI_BOLD}Day {day}{ANSI_RESET} · lines {}-{end} of {}\n{}\n{ANSI_ITALIC}{PUZZLE_HELP}{ANSI_RESET}",
+            offset + 1,
+            lines.len(),
+            lines[offset..end].join("\n"),
+        )?;
+        stdout.flush()?;
+
+        let max_offset = lines.len().saturating_sub(height);
+        match keys.recv() {
+            Ok(Key::Up) => offset = offset.saturating_sub(1),
+            Ok(Key::Down) => offset = (offset + 1).min(max_offset),
+            Ok(Key::PageDown) => offset = (offset + height).min(max_offset),
+            Ok(Key::Open | Key::Quit) | Err(_) => return Ok(()),
+            Ok(_) => {}
+        }
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{key_decoder, pad, Dashboard, DayState, Key};
+    use crate::day;
+    use crate::template::readme_benchmarks::Timings;
+
+    #[test]
+    fn decodes_keys() {
+        let mut decode = key_decoder();
+        let keys = b"j\x1b[A\x1b[Bsq"
+            .iter()
+            .filter_map(|byte| decode(*byte))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            keys,
+            vec![Key::Down, Key::Up, Key::Down, Key::Submit(1), Key::Quit]
+        );
+    }
+
+    #[test]
+    fn marks_verified_answers() {
+        let state = DayState {
+            scaffolded: true,
+            expected: [Some("142".into()), Some("281".into())],
+            answers: [Some("142".into()), Some("280".into())],
+            ..DayState::default()
+        };
+
+        assert_eq!(state.answer(0), "142 ✓");
+        assert_eq!(state.answer(1), "280 ✗");
+    }
+
+    #[test]
+    fn pads_and_truncates() {
+        assert_eq!(pad("\x1b[3m42\x1b[0m", 4), "\x1b[3m42\x1b[0m  ");
+        assert_eq!(pad("123456", 4), "123…");
+    }
+
+    #[test]
+    fn seeds_timings() {
+        let timings = Timings {
+            day: day!(1),
+            part_1: Some("1.2ms".into()),
+            part_2: None,
+            total_nanos: 1_200_000.0,
+            part_1_memory: None,
+            part_2_memory: None,
+        };
+
+        let state = DayState::load(day!(1), Some(&timings));
+        assert_eq!(state.timings, [Some("1.2ms".into()), None]);
+
+        let state = DayState::load(day!(2), None);
+        assert_eq!(state.timings, [None, None]);
+    }
+
+    #[test]
+    fn renders_days() {
+        let dashboard = Dashboard {
+            days: vec![
+                (
+                    day!(1),
+                    DayState {
+                        scaffolded: true,
+                        has_input: true,
+                        answers: [Some("142".into()), None],
+                        timings: [Some("1.2ms".into()), None],
+                        tests: Some(true),
+                        ..DayState::default()
+                    },
+                ),
+                (day!(2), DayState::default()),
+            ],
+            selected: 1,
+            message: "Solved day 01.".into(),
+        };
+
+        let screen = crate::template::runner::strip_ansi(&dashboard.render());
+        let lines = screen.lines().collect::<Vec<_>>();
+
+        assert!(lines[3].starts_with("01   ready     142"));
+        assert!(lines[3].ends_with("1.2ms               ✓"));
+        assert!(lines[4].starts_with("02   -         -"));
+        assert!(screen.contains("Solved day 01."));
+    }
+}
@@ -2,13 +2,13 @@ use crate::template::aoc_cli;
 use crate::Day;
 use std::process;
 
-pub fn handle(day: Day) {
+pub fn handle(day: Day, input_set: Option<&str>, session_file: Option<&str>) {
     if aoc_cli::check().is_err() {
         eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
         process::exit(1);
     }
 
-    if let Err(e) = aoc_cli::download(day) {
+    if let Err(e) = aoc_cli::download(day, input_set, session_file) {
         eprintln!("failed to call aoc-cli: {e}");
         process::exit(1);
     };
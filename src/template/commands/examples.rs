@@ -0,0 +1,354 @@
+use std::io::{self, BufRead, IsTerminal, Write};
+use std::{fs, process};
+
+use crate::template::aoc_cli;
+use crate::template::commands::scaffold::{plan_data_file, run_operations, Action, Operation};
+use crate::template::puzzle::{self, PuzzlePart};
+use crate::Day;
+
+/// The example and expected answer chosen for one part of a puzzle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct Selection {
+    pub example: String,
+    pub answer: Option<String>,
+}
+
+/// Examples chosen for both parts. Part two has its own example (`NN-2.txt`) only if it differs
+/// from the example of part one.
+#[derive(Debug, Default)]
+pub(super) struct Examples {
+    pub part_one: Option<Selection>,
+    pub part_two: Option<Selection>,
+}
+
+impl Examples {
+    fn part_two_example(&self) -> Option<&str> {
+        let part_two = self.part_two.as_ref()?;
+        match &self.part_one {
+            Some(part_one) if part_one.example == part_two.example => None,
+            _ => Some(&part_two.example),
+        }
+    }
+}
+
+/// Chooses the example and answer of each part from the parsed puzzle description. If a part
+/// contains more than one candidate, the user is asked to pick one. Without a terminal, the first
+/// code block and the last highlighted answer are used.
+pub(super) fn select(day: Day, parts: &[PuzzlePart]) -> Examples {
+    let mut examples = Examples::default();
+
+    for (index, part) in parts.iter().take(2).enumerate() {
+        let part_number = index + 1;
+
+        let example = match part.examples.len() {
+            0 if part_number == 2 => examples.part_one.as_ref().map(|x| x.example.clone()),
+            0 => None,
+            _ => {
+                let choice = pick(
+                    &format!(
+                        "Day {day}, part {part_number}: which code block is the example input?"
+                    ),
+                    &part.examples,
+                    0,
+                );
+                Some(part.examples[choice].clone())
+            }
+        };
+
+        let Some(example) = example else {
+            continue;
+        };
+
+        let answer = match part.answers.len() {
+            0 => None,
+            count => {
+                let choice = pick(
+                    &format!("Day {day}, part {part_number}: which value is the expected answer?"),
+                    &part.answers,
+                    count - 1,
+                );
+                Some(part.answers[choice].clone())
+            }
+        };
+
+        let selection = Some(Selection { example, answer });
+        if part_number == 1 {
+            examples.part_one = selection;
+        } else {
+            examples.part_two = selection;
+        }
+    }
+
+    examples
+}
+
+/// Asks the user to pick one of `options`. Returns `default` if there is nothing to choose from or
+/// stdin is not a terminal.
+fn pick(prompt: &str, options: &[String], default: usize) -> usize {
+    if options.len() < 2 {
+        return 0;
+    }
+
+    if !io::stdin().is_terminal() {
+        eprintln!(
+            "{prompt} Found {} candidates, using #{}.",
+            options.len(),
+            default + 1
+        );
+        return default;
+    }
+
+    println!("{prompt}");
+    for (index, option) in options.iter().enumerate() {
+        println!("  [{}] {}", index + 1, preview(option));
+    }
+
+    loop {
+        print!("Choice [{}]: ", default + 1);
+        let _ = io::stdout().flush();
+
+        let mut line = String::new();
+        if io::stdin().lock().read_line(&mut line).unwrap_or(0) == 0 {
+            return default;
+        }
+
+        match line.trim() {
+            "" => return default,
+            choice => match choice.parse::<usize>() {
+                Ok(choice) if (1..=options.len()).contains(&choice) => return choice - 1,
+                _ => println!("Please enter a number between 1 and {}.", options.len()),
+            },
+        }
+    }
+}
+
+/// A single-line preview of a candidate: the first line and the number of lines of code blocks.
+fn preview(option: &str) -> String {
+    let mut lines = option.lines();
+    let first = lines.next().unwrap_or_default();
+    let first = match first.char_indices().nth(40) {
+        Some((index, _)) => format!("{}…", &first[..index]),
+        None => first.to_string(),
+    };
+
+    match lines.count() {
+        0 => first,
+        rest => format!("{first} (+{rest} lines)"),
+    }
+}
+
+/// Formats an answer as the expected value of a test assertion.
+fn answer_literal(answer: &str) -> String {
+    if answer.parse::<i128>().is_ok() {
+        format!("Some({answer})")
+    } else {
+        format!("Some({answer:?}.to_string())")
+    }
+}
+
+/// Updates the test of `part` in a solution module: a `None` assertion is replaced with the
+/// expected answer, and the test of part two reads `NN-2.txt` if it has its own example.
+/// Assertions that were already changed by hand are left alone.
+fn update_module_tests(module: &str, part: u8, answer: Option<&str>, part_example: bool) -> String {
+    let test_name = match part {
+        1 => "fn test_part_one()",
+        _ => "fn test_part_two()",
+    };
+
+    let Some(start) = module.find(test_name) else {
+        return module.to_string();
+    };
+
+    let end = module[start + test_name.len()..]
+        .find("fn ")
+        .map_or(module.len(), |end| start + test_name.len() + end);
+
+    let mut test = module[start..end].to_string();
+
+    if let Some(answer) = answer {
+        test = test.replacen(
+            "assert_eq!(result, None);",
+            &format!("assert_eq!(result, {});", answer_literal(answer)),
+            1,
+        );
+    }
+
+    if part_example {
+        test = test.replacen(
+            "read_file(\"examples\", DAY)",
+            &format!("read_file_part(\"examples\", DAY, {part})"),
+            1,
+        );
+    }
+
+    format!("{}{test}{}", &module[..start], &module[end..])
+}
+
+/// Applies the chosen examples to the tests of a solution module.
+pub(super) fn update_module(module: &str, examples: &Examples) -> String {
+    let part_one = examples.part_one.as_ref().and_then(|x| x.answer.as_deref());
+    let part_two = examples.part_two.as_ref().and_then(|x| x.answer.as_deref());
+
+    let module = update_module_tests(module, 1, part_one, false);
+    update_module_tests(&module, 2, part_two, examples.part_two_example().is_some())
+}
+
+/// Plans writing the example files of a day, `NN.txt` and, if part two has its own example,
+/// `NN-2.txt`.
+pub(super) fn plan_example_files(
+    day: Day,
+    examples: &Examples,
+    force: bool,
+) -> Result<Vec<Operation>, String> {
+    let mut operations = vec![plan_data_file(
+        format!("data/examples/{day}.txt"),
+        "example file",
+        examples
+            .part_one
+            .as_ref()
+            .map(|x| x.example.clone())
+            .unwrap_or_default(),
+        force,
+    )?];
+
+    if let Some(example) = examples.part_two_example() {
+        operations.push(plan_data_file(
+            format!("data/examples/{day}-2.txt"),
+            "example file",
+            example.to_string(),
+            force,
+        )?);
+    }
+
+    Ok(operations)
+}
+
+pub fn handle(day: Day, force: bool, dry_run: bool) {
+    let Some(parts) = puzzle::read(day) else {
+        eprintln!(
+            "Could not read \"{}\". Run `cargo download {day}` first.",
+            aoc_cli::get_puzzle_path(day)
+        );
+        process::exit(1);
+    };
+
+    let examples = select(day, &parts);
+
+    if examples.part_one.is_none() {
+        eprintln!("No examples found in the description of day {day}.");
+        process::exit(1);
+    }
+
+    let mut operations = match plan_example_files(day, &examples, force) {
+        Ok(operations) => operations,
+        Err(e) => {
+            eprintln!("Failed to extract examples for day {day}: {e}");
+            process::exit(1);
+        }
+    };
+
+    let module_path = format!("src/bin/{day}.rs");
+    if let Ok(module) = fs::read_to_string(&module_path) {
+        let updated = update_module(&module, &examples);
+        if updated != module {
+            operations.push(Operation {
+                path: module_path,
+                description: "tests in",
+                action: Action::Update,
+                contents: updated,
+            });
+        }
+    }
+
+    for (part, selection) in [(1, &examples.part_one), (2, &examples.part_two)] {
+        if let Some(answer) = selection.as_ref().and_then(|x| x.answer.as_ref()) {
+            println!("Part {part}: expecting {answer} for the example.");
+        }
+    }
+
+    run_operations(&operations, dry_run);
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{answer_literal, preview, update_module, Examples, Selection};
+
+    const MODULE: &str = r#"advent_of_code::solution!(1);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        assert_eq!(result, None);
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        assert_eq!(result, None);
+    }
+}
+"#;
+
+    fn selection(example: &str, answer: &str) -> Option<Selection> {
+        Some(Selection {
+            example: example.into(),
+            answer: Some(answer.into()),
+        })
+    }
+
+    #[test]
+    fn updates_assertions() {
+        let examples = Examples {
+            part_one: selection("a\n", "142"),
+            part_two: selection("a\n", "281"),
+        };
+        let updated = update_module(MODULE, &examples);
+
+        assert!(updated
+            .contains("read_file(\"examples\", DAY));\n        assert_eq!(result, Some(142));"));
+        assert!(updated
+            .contains("read_file(\"examples\", DAY));\n        assert_eq!(result, Some(281));"));
+        assert_eq!(update_module(&updated, &examples), updated);
+    }
+
+    #[test]
+    fn uses_part_two_example() {
+        let examples = Examples {
+            part_one: selection("a\n", "142"),
+            part_two: selection("b\n", "281"),
+        };
+        let updated = update_module(MODULE, &examples);
+
+        assert!(updated
+            .contains("read_file(\"examples\", DAY));\n        assert_eq!(result, Some(142));"));
+        assert!(updated.contains(
+            "read_file_part(\"examples\", DAY, 2));\n        assert_eq!(result, Some(281));"
+        ));
+    }
+
+    #[test]
+    fn leaves_missing_parts_alone() {
+        let examples = Examples {
+            part_one: selection("a\n", "142"),
+            part_two: None,
+        };
+        let updated = update_module(MODULE, &examples);
+        assert!(updated.ends_with("DAY));\n        assert_eq!(result, None);\n    }\n}\n"));
+    }
+
+    #[test]
+    fn formats_answers() {
+        assert_eq!(answer_literal("-12"), "Some(-12)");
+        assert_eq!(answer_literal("A,B"), "Some(\"A,B\".to_string())");
+    }
+
+    #[test]
+    fn previews_candidates() {
+        assert_eq!(preview("467..114..\n...*......\n"), "467..114.. (+1 lines)");
+        assert_eq!(preview("142"), "142");
+    }
+}
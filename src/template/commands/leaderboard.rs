@@ -0,0 +1,134 @@
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{self, Command, Stdio};
+use std::time::Duration;
+use std::{env, fs};
+
+use crate::template::aoc_cli;
+use crate::template::leaderboard::{Leaderboard, SortBy};
+use crate::Day;
+
+const CACHE_DIR: &str = "data/leaderboards";
+
+/// The website asks not to request private leaderboards more often than every 15 minutes.
+const MAX_AGE: Duration = Duration::from_secs(15 * 60);
+
+/// Default location of the session cookie, shared with aoc-cli.
+const SESSION_FILE: &str = ".adventofcode.session";
+
+#[derive(Default)]
+pub struct Options {
+    /// Read the leaderboard from a JSON file instead of fetching it.
+    pub file: Option<String>,
+    /// Show the times of one day instead of the overview.
+    pub day: Option<Day>,
+    pub sort: SortBy,
+    /// Fetch the leaderboard even if the cached copy is recent.
+    pub refresh: bool,
+    pub session_file: Option<String>,
+}
+
+pub fn handle(id: &str, options: &Options) {
+    let json = match &options.file {
+        Some(file) => {
+            fs::read_to_string(file).map_err(|e| format!("could not read \"{file}\": {e}"))
+        }
+        None => load(id, options),
+    };
+
+    let result = json
+        .and_then(|json| Leaderboard::parse(&json))
+        .and_then(|leaderboard| match options.day {
+            Some(day) => leaderboard.render_day(day, options.sort),
+            None => leaderboard.render_overview(options.sort),
+        });
+
+    match result {
+        Ok(output) => print!("{output}"),
+        Err(e) => {
+            eprintln!("Failed to show leaderboard: {e}");
+            process::exit(1);
+        }
+    }
+}
+
+/// Returns the cached copy of the leaderboard in `data/leaderboards/<id>.json` if it is recent,
+/// and fetches it otherwise. A stale copy is used if fetching fails.
+fn load(id: &str, options: &Options) -> Result<String, String> {
+    let cache_path = Path::new(CACHE_DIR).join(format!("{id}.json"));
+
+    let age = fs::metadata(&cache_path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+        .and_then(|modified| modified.elapsed().ok());
+
+    if !options.refresh && age.is_some_and(|age| age < MAX_AGE) {
+        return fs::read_to_string(&cache_path).map_err(|e| e.to_string());
+    }
+
+    match fetch(id, options.session_file.as_deref()) {
+        Ok(json) => {
+            fs::create_dir_all(CACHE_DIR)
+                .and_then(|()| fs::write(&cache_path, &json))
+                .map_err(|e| format!("could not cache leaderboard: {e}"))?;
+            Ok(json)
+        }
+        Err(e) if age.is_some() => {
+            eprintln!("Failed to fetch leaderboard, using the cached copy: {e}");
+            fs::read_to_string(&cache_path).map_err(|e| e.to_string())
+        }
+        Err(e) => Err(e),
+    }
+}
+
+fn fetch(id: &str, session_file: Option<&str>) -> Result<String, String> {
+    let year = aoc_cli::get_year().ok_or("AOC_YEAR is not set")?;
+
+    let session_file = session_file.map_or_else(
+        || env::var("HOME").map(|home| PathBuf::from(home).join(SESSION_FILE)),
+        |file| Ok(PathBuf::from(file)),
+    );
+    let session = session_file
+        .ok()
+        .and_then(|file| fs::read_to_string(file).ok())
+        .filter(|session| !session.trim().is_empty())
+        .ok_or("could not read the session cookie, pass it with --session-file")?;
+
+    println!("Fetching private leaderboard {id} of {year}...");
+
+    // the cookie header is passed on stdin, so the session does not show up in the process list.
+    let mut child = Command::new("curl")
+        .args(["--fail", "--silent", "--show-error", "--header", "@-"])
+        .arg(format!(
+            "https://adventofcode.com/{year}/leaderboard/private/view/{id}.json"
+        ))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("could not call curl: {e}"))?;
+
+    child
+        .stdin
+        .take()
+        .expect("stdin is piped")
+        .write_all(format!("Cookie: session={}\n", session.trim()).as_bytes())
+        .map_err(|e| format!("could not pass the session cookie to curl: {e}"))?;
+
+    let output = child
+        .wait_with_output()
+        .map_err(|e| format!("could not call curl: {e}"))?;
+
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+    }
+
+    let json = String::from_utf8_lossy(&output.stdout).to_string();
+
+    // the website redirects to the login page if the session expired.
+    if !json.trim_start().starts_with('{') {
+        return Err("the session cookie is invalid or expired".into());
+    }
+
+    Ok(json)
+}
@@ -1,5 +1,15 @@
 pub mod all;
+pub mod crossval;
+pub mod dashboard;
 pub mod download;
+pub mod examples;
+pub mod leaderboard;
+#[cfg(feature = "profile")]
+mod profile;
 pub mod read;
+pub mod render;
 pub mod scaffold;
 pub mod solve;
+pub mod stars;
+pub mod verify;
+mod watch;
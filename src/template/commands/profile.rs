@@ -0,0 +1,166 @@
+use std::fs;
+use std::path::PathBuf;
+use std::process::{self, Command, Stdio};
+
+use crate::template::profiler;
+use crate::Day;
+
+/// Hardware counters shown by `perf stat`.
+const COUNTERS: &str = "cycles,instructions,cache-references,cache-misses,branches,branch-misses";
+
+/// Profiles one part of a solution and writes a flamegraph to `target/profiles/`. `args` are
+/// passed to the solution binary, e.g. to select an input. Only available with the `profile`
+/// feature, see [`super::solve`].
+pub fn handle(day: Day, part: u8, args: &[String]) {
+    if !(1..=2).contains(&part) {
+        eprintln!("--profile must be 1 or 2.");
+        process::exit(1);
+    }
+
+    let folded = if is_perf_available() {
+        profile_perf(day, part, args).or_else(|e| {
+            eprintln!("Failed to profile with perf, using the built-in sampler instead: {e}");
+            profile_builtin(day, part, args)
+        })
+    } else {
+        println!("perf was not found, using the built-in sampler.");
+        profile_builtin(day, part, args)
+    };
+
+    let path = profiler::get_svg_path(day, part);
+
+    let result = folded.and_then(|folded| {
+        profiler::write_flamegraph(&folded, &format!("Day {day}, part {part}"), &path)
+    });
+
+    match result {
+        Ok(()) => println!("🔥 Wrote flamegraph to \"{}\".", path.display()),
+        Err(e) => {
+            eprintln!("Failed to profile part {part} of day {day}: {e}");
+            process::exit(1);
+        }
+    }
+}
+
+fn is_perf_available() -> bool {
+    Command::new("perf")
+        .arg("--version")
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .is_ok_and(|status| status.success())
+}
+
+/// Builds the solution with debug info, so that the frames of the flamegraph have names.
+fn build(day: Day, features: &[&str]) -> Result<(), String> {
+    let mut args = vec!["build", "--quiet", "--profile", "profiling"];
+    for feature in features {
+        args.extend(["--features", feature]);
+    }
+
+    let day = day.to_string();
+    args.extend(["--bin", &day]);
+
+    let status = Command::new("cargo")
+        .args(&args)
+        .status()
+        .map_err(|e| e.to_string())?;
+
+    if status.success() {
+        Ok(())
+    } else {
+        Err("could not build the solution".into())
+    }
+}
+
+fn get_binary_path(day: Day) -> PathBuf {
+    profiler::get_target_dir()
+        .join("profiling")
+        .join(day.to_string())
+}
+
+fn profile_perf(day: Day, part: u8, args: &[String]) -> Result<Vec<String>, String> {
+    build(day, &[])?;
+
+    let binary = get_binary_path(day);
+    let part = part.to_string();
+    let profile_dir = profiler::get_profile_dir();
+    let data = profile_dir.join(format!("{day}-{part}.perf.data"));
+    let stat = profile_dir.join(format!("{day}-{part}.stat.txt"));
+
+    fs::create_dir_all(&profile_dir).map_err(|e| e.to_string())?;
+
+    let status = Command::new("perf")
+        .args([
+            "record",
+            "--quiet",
+            "-g",
+            "-F",
+            &profiler::FREQUENCY.to_string(),
+        ])
+        .arg("-o")
+        .arg(&data)
+        .arg("--")
+        .arg(&binary)
+        .args(args)
+        .args(["--profile", &part])
+        .status()
+        .map_err(|e| e.to_string())?;
+
+    if !status.success() {
+        return Err("perf record failed, check /proc/sys/kernel/perf_event_paranoid".into());
+    }
+
+    let script = Command::new("perf")
+        .arg("script")
+        .arg("-i")
+        .arg(&data)
+        .stderr(Stdio::null())
+        .output()
+        .map_err(|e| e.to_string())?;
+
+    if !script.status.success() {
+        return Err("perf script failed".into());
+    }
+
+    // the counters are informative only, the flamegraph does not depend on them.
+    let counters = Command::new("perf")
+        .args(["stat", "-e", COUNTERS])
+        .arg("-o")
+        .arg(&stat)
+        .arg("--")
+        .arg(&binary)
+        .args(args)
+        .args(["--profile", &part])
+        .stdout(Stdio::null())
+        .status();
+
+    match counters {
+        Ok(status) if status.success() => {
+            if let Ok(stat) = fs::read_to_string(&stat) {
+                print!("{stat}");
+            }
+        }
+        _ => eprintln!("Failed to read hardware counters."),
+    }
+
+    profiler::collapse_perf(&String::from_utf8_lossy(&script.stdout)).map_err(|e| e.to_string())
+}
+
+fn profile_builtin(day: Day, part: u8, args: &[String]) -> Result<Vec<String>, String> {
+    build(day, &["profile"])?;
+
+    let status = Command::new(get_binary_path(day))
+        .args(args)
+        .args(["--profile", &part.to_string()])
+        .status()
+        .map_err(|e| e.to_string())?;
+
+    if !status.success() {
+        return Err("the solution failed".into());
+    }
+
+    let folded =
+        fs::read_to_string(profiler::get_folded_path(day, part)).map_err(|e| e.to_string())?;
+    Ok(folded.lines().map(ToString::to_string).collect())
+}
@@ -1,16 +1,68 @@
-use std::process;
+use std::path::Path;
+use std::{fs, process};
 
-use crate::template::aoc_cli;
+use crate::template::cache::{self, Cache};
+use crate::template::{aoc_cli, markdown, puzzle, terminal};
 use crate::Day;
 
-pub fn handle(day: Day) {
+/// Widest column paragraphs are wrapped at, for readability on wide terminals.
+const MAX_WIDTH: usize = 100;
+
+/// Shows the puzzle description saved by `download`, or only one of its parts. Falls back to
+/// fetching the description with aoc-cli if it wasn't downloaded yet, or if the requested part is
+/// missing from the cached description.
+pub fn handle(day: Day, part: Option<u8>) {
+    if part.is_some_and(|part| !(1..=2).contains(&part)) {
+        eprintln!("--part must be 1 or 2.");
+        process::exit(1);
+    }
+
+    let cache = Cache::load().unwrap_or_default();
+    let path = aoc_cli::get_puzzle_path(day);
+
+    if !Path::new(&path).exists() {
+        eprintln!("\"{path}\" does not exist, fetching it with aoc-cli.");
+        fetch(day);
+    } else if let Some(part) = part.filter(|part| !cache.contains(day, *part)) {
+        eprintln!("\"{path}\" does not contain part {part}, fetching it with aoc-cli.");
+        fetch(day);
+    }
+
+    match render_cached(day, part) {
+        Ok(puzzle) => print!("{puzzle}"),
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    }
+}
+
+/// Downloads the description into the cache, leaving the input alone.
+fn fetch(day: Day) {
     if aoc_cli::check().is_err() {
         eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
         process::exit(1);
     }
 
-    if let Err(e) = aoc_cli::read(day) {
+    if let Err(e) = cache::refresh_description(day) {
         eprintln!("failed to call aoc-cli: {e}");
         process::exit(1);
     };
 }
+
+fn render_cached(day: Day, part: Option<u8>) -> Result<String, String> {
+    let path = aoc_cli::get_puzzle_path(day);
+    let description =
+        fs::read_to_string(&path).map_err(|e| format!("could not read \"{path}\": {e}"))?;
+
+    let markdown = match part {
+        None => description,
+        Some(part) => puzzle::split_parts(&description)
+            .into_iter()
+            .nth(usize::from(part) - 1)
+            .ok_or_else(|| format!("Part {part} is not unlocked yet."))?,
+    };
+
+    let width = terminal::size().map_or(80, |(_, columns)| columns);
+    Ok(markdown::render(&markdown, width.min(MAX_WIDTH)))
+}
@@ -0,0 +1,18 @@
+use crate::template::commands::solve;
+use crate::Day;
+
+/// Image path used if no `--output` is passed. The part is appended to the file name.
+fn get_default_output_path(day: Day) -> String {
+    format!("target/renders/{day}.svg")
+}
+
+pub fn handle(day: Day, release: bool, output: Option<String>) {
+    solve::handle(
+        day,
+        solve::Options {
+            release,
+            render: Some(output.unwrap_or_else(|| get_default_output_path(day))),
+            ..Default::default()
+        },
+    );
+}
@@ -1,18 +1,22 @@
 use std::{
-    fs::{File, OpenOptions},
+    fs::{self, File, OpenOptions},
     io::Write,
+    path::Path,
     process,
 };
 
+use crate::template::commands::examples;
+use crate::template::{aoc_cli, encryption, puzzle};
 use crate::Day;
 
-const MODULE_TEMPLATE: &str = r#"advent_of_code::solution!(DAY_NUMBER);
+/// Built-in module template, used if the project does not define its own.
+const MODULE_TEMPLATE: &str = r#"advent_of_code::solution!({{day}});
 
-pub fn part_one(input: &str) -> Option<u32> {
+pub fn part_one(input: &str) -> Option<{{answer_type}}> {
     None
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
+pub fn part_two(input: &str) -> Option<{{answer_type}}> {
     None
 }
 
@@ -34,61 +38,606 @@ mod tests {
 }
 "#;
 
+/// Answer type used by the module template if none is passed via `--answer`.
+pub const DEFAULT_ANSWER_TYPE: &str = "u32";
+
+/// Directory that holds project-specific module templates, e.g. `.aoc/templates/default.rs`.
+const TEMPLATE_DIR: &str = ".aoc/templates";
+
+/// Name of the project template used if none is passed via `--template`.
+const DEFAULT_TEMPLATE_NAME: &str = "default";
+
+/// Loads the module template: the one named by `--template`, otherwise the project's default
+/// template if present, otherwise the built-in template.
+fn load_template(name: Option<&str>) -> Result<String, String> {
+    let path =
+        Path::new(TEMPLATE_DIR).join(format!("{}.rs", name.unwrap_or(DEFAULT_TEMPLATE_NAME)));
+
+    match fs::read_to_string(&path) {
+        Ok(template) => Ok(template),
+        Err(_) if name.is_none() => Ok(MODULE_TEMPLATE.into()),
+        Err(e) => Err(format!(
+            "could not read template \"{}\": {e}",
+            path.display()
+        )),
+    }
+}
+
+/// Reads the puzzle title from the description saved by `download`, e.g. "Trebuchet?!".
+fn read_puzzle_title(day: Day) -> Option<String> {
+    let puzzle = fs::read_to_string(aoc_cli::get_puzzle_path(day)).ok()?;
+    parse_puzzle_title(&puzzle)
+}
+
+fn parse_puzzle_title(puzzle: &str) -> Option<String> {
+    puzzle.lines().find_map(|line| {
+        let (_, title) = line.split_once("--- Day ")?.1.split_once(": ")?;
+        Some(title.trim_end_matches('-').trim().to_string())
+    })
+}
+
+/// Substitutes `{{name}}` placeholders in a template. Only the names in `variables` are replaced,
+/// other braces (e.g. `format!("{{}}")` in the template's code) are left as they are.
+fn render_template(template: &str, variables: &[(&str, String)]) -> String {
+    let mut rendered = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find("{{") {
+        let value = rest[start + 2..].find("}}").and_then(|end| {
+            let name = rest[start + 2..start + 2 + end].trim();
+            variables
+                .iter()
+                .find(|(key, _)| *key == name)
+                .map(|(_, value)| (value, start + 2 + end + 2))
+        });
+
+        match value {
+            Some((value, end)) => {
+                rendered.push_str(&rest[..start]);
+                rendered.push_str(value);
+                rest = &rest[end..];
+            }
+            None => {
+                rendered.push_str(&rest[..start + 2]);
+                rest = &rest[start + 2..];
+            }
+        }
+    }
+
+    rendered.push_str(rest);
+    rendered
+}
+
+/// Debug configurations for VS Code, see `.vscode/launch.json`.
+const LAUNCH_CONFIG_PATH: &str = ".vscode/launch.json";
+
+const LAUNCH_CONFIG_TEMPLATE: &str = r#"        {
+            "type": "lldb",
+            "request": "launch",
+            "name": "Debug executable '{{day}}'",
+            "cargo": {
+                "args": ["build", "--bin={{day}}", "--package=advent_of_code"],
+                "filter": {
+                    "name": "{{day}}",
+                    "kind": "bin"
+                }
+            },
+            "args": [],
+            "cwd": "${workspaceFolder}"
+        },
+        {
+            "type": "lldb",
+            "request": "launch",
+            "name": "Debug unit tests in executable '{{day}}'",
+            "cargo": {
+                "args": ["test", "--no-run", "--bin={{day}}", "--package=advent_of_code"],
+                "filter": {
+                    "name": "{{day}}",
+                    "kind": "bin"
+                }
+            },
+            "args": [],
+            "cwd": "${workspaceFolder}"
+        }"#;
+
+/// Manifest that declares the bench targets.
+const MANIFEST_PATH: &str = "Cargo.toml";
+
+/// Heads the `[[bench]]` tables in the manifest. The tables are rewritten by `scaffold` from the
+/// files in `benches/`, so they can't drift apart.
+const BENCH_TARGETS_COMMENT: &str =
+    "# benchmarks of the solutions, one per file in `benches/`. Managed by `cargo scaffold`, run with `cargo bench --bench <day>`.";
+
+/// Bench target of a day, see [`crate::benchmark`].
+const BENCH_TEMPLATE: &str = r#"#[allow(dead_code, unused_imports)]
+mod solution {
+    include!("../src/bin/{{day}}.rs");
+
+    advent_of_code::benchmark!(part_one, part_two);
+}
+
+criterion::criterion_group!(benches, solution::benchmark);
+criterion::criterion_main!(benches);
+"#;
+
+/// Options of the `scaffold` command.
+#[derive(Default)]
+pub struct Options<'a> {
+    pub answer_type: &'a str,
+    pub template: Option<&'a str>,
+    /// Overwrite an existing module and non-empty data files.
+    pub force: bool,
+    /// Print the planned file operations without touching any files.
+    pub dry_run: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum Action {
+    Create,
+    Overwrite,
+    Update,
+    Keep,
+}
+
+/// A file write planned by `scaffold`. All operations are planned up front so that nothing is
+/// written if one of them would clobber an existing file.
+pub(super) struct Operation {
+    pub path: String,
+    pub description: &'static str,
+    pub action: Action,
+    pub contents: String,
+}
+
+impl Operation {
+    pub fn describe(&self, dry_run: bool) -> String {
+        let verb = match (self.action, dry_run) {
+            (Action::Create, true) => "Create",
+            (Action::Create, false) => "Created",
+            (Action::Overwrite, true) => "Overwrite",
+            (Action::Overwrite, false) => "Overwrote",
+            (Action::Update, true) => "Update",
+            (Action::Update, false) => "Updated",
+            (Action::Keep, _) => "Keep existing",
+        };
+
+        format!("{verb} {} \"{}\"", self.description, self.path)
+    }
+
+    pub fn execute(&self) -> Result<(), std::io::Error> {
+        match self.action {
+            Action::Create => safe_create_file(&self.path)?.write_all(self.contents.as_bytes()),
+            Action::Overwrite | Action::Update => fs::write(&self.path, &self.contents),
+            Action::Keep => Ok(()),
+        }
+    }
+}
+
 fn safe_create_file(path: &str) -> Result<File, std::io::Error> {
     OpenOptions::new().write(true).create_new(true).open(path)
 }
 
-fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new().write(true).create(true).open(path)
-}
+/// Plans writing the module file, which is never overwritten unless `--force` is passed.
+fn plan_module(path: String, module: String, force: bool) -> Result<Operation, String> {
+    let action = match (Path::new(&path).exists(), force) {
+        (false, _) => Action::Create,
+        (true, true) => Action::Overwrite,
+        (true, false) => {
+            return Err(format!(
+                "module file \"{path}\" already exists. Use --force to overwrite it."
+            ))
+        }
+    };
 
-pub fn handle(day: Day) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+    Ok(Operation {
+        path,
+        description: "module file",
+        action,
+        contents: module,
+    })
+}
 
-    let mut file = match safe_create_file(&module_path) {
-        Ok(file) => file,
-        Err(e) => {
-            eprintln!("Failed to create module file: {e}");
-            process::exit(1);
+/// Plans writing a data file. Files that already have the planned contents are kept, and empty
+/// files are filled in. Files with other contents are only overwritten if `--force` is passed.
+pub(super) fn plan_data_file(
+    path: String,
+    description: &'static str,
+    contents: String,
+    force: bool,
+) -> Result<Operation, String> {
+    let action = match (fs::read_to_string(&path), force) {
+        (Err(_), _) => Action::Create,
+        (Ok(existing), _) if existing == contents => Action::Keep,
+        (Ok(existing), _) if existing.is_empty() => Action::Update,
+        (Ok(_), true) => Action::Overwrite,
+        (Ok(_), false) => {
+            return Err(format!(
+                "{description} \"{path}\" is not empty. Use --force to overwrite it."
+            ))
         }
     };
 
-    match file.write_all(
-        MODULE_TEMPLATE
-            .replace("DAY_NUMBER", &day.into_inner().to_string())
-            .as_bytes(),
-    ) {
-        Ok(()) => {
-            println!("Created module file \"{}\"", &module_path);
+    Ok(Operation {
+        path,
+        description,
+        action,
+        contents,
+    })
+}
+
+/// Plans creating an empty input file. An existing input is always kept, even with `--force`, as
+/// it is usually the downloaded puzzle input. No plaintext is created next to an encrypted input.
+fn plan_input_file(path: String) -> Operation {
+    let encrypted_path = encryption::encrypted_path(Path::new(&path));
+
+    let (path, action) = if encrypted_path.exists() {
+        (encrypted_path.display().to_string(), Action::Keep)
+    } else if Path::new(&path).exists() {
+        (path, Action::Keep)
+    } else {
+        (path, Action::Create)
+    };
+
+    Operation {
+        path,
+        description: "input file",
+        action,
+        contents: String::new(),
+    }
+}
+
+/// Plans writing the bench target of the day, which is kept if it exists unless `--force` is
+/// passed, and declaring it in `Cargo.toml`. Criterion needs its own `main`, so every bench target
+/// has to be declared with `harness = false`.
+fn plan_bench_target(day: Day, force: bool) -> Vec<Operation> {
+    let path = format!("benches/{day}.rs");
+
+    let action = match (Path::new(&path).exists(), force) {
+        (false, _) => Action::Create,
+        (true, true) => Action::Overwrite,
+        (true, false) => Action::Keep,
+    };
+
+    let mut operations = vec![Operation {
+        path,
+        description: "bench target",
+        action,
+        contents: render_template(BENCH_TEMPLATE, &[("day", day.to_string())]),
+    }];
+
+    let mut days = list_bench_days();
+    days.push(day);
+
+    if let Some(contents) = fs::read_to_string(MANIFEST_PATH)
+        .ok()
+        .and_then(|manifest| write_bench_targets(&manifest, &days))
+    {
+        operations.push(Operation {
+            path: MANIFEST_PATH.into(),
+            description: "bench targets in",
+            action: Action::Update,
+            contents,
+        });
+    }
+
+    operations
+}
+
+/// Days with a bench target file in `benches/`.
+fn list_bench_days() -> Vec<Day> {
+    let Ok(entries) = fs::read_dir("benches") else {
+        return vec![];
+    };
+
+    entries
+        .filter_map(Result::ok)
+        .filter_map(|entry| {
+            let path = entry.path();
+            (path.extension()? == "rs").then_some(())?;
+            path.file_stem()?.to_str()?.parse().ok()
+        })
+        .collect()
+}
+
+/// Replaces the `[[bench]]` tables of the manifest with one table per day, in order, at the end of
+/// the manifest. Returns `None` if the manifest already declares exactly these tables.
+fn write_bench_targets(manifest: &str, days: &[Day]) -> Option<String> {
+    let mut days = days.to_vec();
+    days.sort();
+    days.dedup();
+
+    let mut in_bench_table = false;
+    let rest = manifest
+        .lines()
+        .filter(|line| {
+            if line.starts_with('[') {
+                in_bench_table = line.trim() == "[[bench]]";
+            }
+            !in_bench_table && *line != BENCH_TARGETS_COMMENT
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let tables = days
+        .iter()
+        .map(|day| format!("[[bench]]\nname = \"{day}\"\nharness = false\n"))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let updated = if days.is_empty() {
+        format!("{}\n", rest.trim_end())
+    } else {
+        format!("{}\n\n{BENCH_TARGETS_COMMENT}\n{tables}", rest.trim_end())
+    };
+
+    (updated != manifest).then_some(updated)
+}
+
+/// Plans adding debug configurations for the day to `.vscode/launch.json`, if the file exists and
+/// does not list the day yet.
+fn plan_launch_config(day: Day) -> Option<Operation> {
+    let launch = fs::read_to_string(LAUNCH_CONFIG_PATH).ok()?;
+
+    add_launch_configurations(&launch, day).map(|contents| Operation {
+        path: LAUNCH_CONFIG_PATH.into(),
+        description: "debug configurations in",
+        action: Action::Update,
+        contents,
+    })
+}
+
+/// Appends the debug configurations for `day` to the `configurations` array of a `launch.json`.
+/// The file may contain comments, so it is edited as text rather than parsed as JSON. Returns
+/// `None` if the day is already configured or the array can't be found.
+fn add_launch_configurations(launch: &str, day: Day) -> Option<String> {
+    if launch.contains(&format!("\"--bin={day}\"")) {
+        return None;
+    }
+
+    let key = launch.find("\"configurations\"")?;
+    let array_start = key + launch[key..].find('[')?;
+    let array_end = find_closing_bracket(launch, array_start)?;
+
+    let head = launch[..array_end].trim_end();
+    let separator = if head.ends_with('[') { "" } else { "," };
+    let configurations = render_template(LAUNCH_CONFIG_TEMPLATE, &[("day", day.to_string())]);
+
+    Some(format!(
+        "{head}{separator}\n{configurations}\n    {}",
+        &launch[array_end..]
+    ))
+}
+
+/// Returns the index of the `]` that closes the `[` at `open`. Brackets in strings and comments
+/// are skipped.
+fn find_closing_bracket(text: &str, open: usize) -> Option<usize> {
+    let mut depth = 0;
+    let mut chars = text[open..].char_indices().peekable();
+
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '[' => depth += 1,
+            ']' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(open + i);
+                }
+            }
+            '"' => {
+                while let Some((_, c)) = chars.next() {
+                    match c {
+                        '\\' => {
+                            chars.next();
+                        }
+                        '"' => break,
+                        _ => {}
+                    }
+                }
+            }
+            '/' if chars.next_if(|&(_, c)| c == '/').is_some() => {
+                chars.find(|&(_, c)| c == '\n');
+            }
+            '/' if chars.next_if(|&(_, c)| c == '*').is_some() => {
+                let mut previous = ' ';
+                chars.find(|&(_, c)| std::mem::replace(&mut previous, c) == '*' && c == '/');
+            }
+            _ => {}
         }
-        Err(e) => {
-            eprintln!("Failed to write module contents: {e}");
-            process::exit(1);
+    }
+
+    None
+}
+
+/// Executes planned operations, or only prints them if `dry_run` is set.
+pub(super) fn run_operations(operations: &[Operation], dry_run: bool) {
+    if dry_run {
+        println!("Dry run, no files were changed. Planned operations:");
+        for operation in operations {
+            println!("  {}", operation.describe(true));
         }
+        return;
     }
 
-    match create_file(&input_path) {
-        Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
+    for operation in operations {
+        match operation.execute() {
+            Ok(()) => println!("{}", operation.describe(false)),
+            Err(e) => {
+                eprintln!(
+                    "Failed to write {} \"{}\": {e}",
+                    operation.description, operation.path
+                );
+                process::exit(1);
+            }
         }
+    }
+}
+
+pub fn handle(day: Day, options: &Options) {
+    let input_path = format!("data/inputs/{day}.txt");
+    let module_path = format!("src/bin/{day}.rs");
+
+    let variables = [
+        ("day", day.into_inner().to_string()),
+        ("day_padded", day.to_string()),
+        (
+            "year",
+            aoc_cli::get_year().map_or_else(String::new, |year| year.to_string()),
+        ),
+        ("title", read_puzzle_title(day).unwrap_or_default()),
+        ("answer_type", options.answer_type.to_string()),
+    ];
+
+    let module = match load_template(options.template)
+        .map(|template| render_template(&template, &variables))
+    {
+        Ok(module) => module,
         Err(e) => {
-            eprintln!("Failed to create input file: {e}");
+            eprintln!("Failed to render module template: {e}");
             process::exit(1);
         }
-    }
+    };
 
-    match create_file(&example_path) {
-        Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
-        }
+    let examples = puzzle::read(day)
+        .map(|parts| examples::select(day, &parts))
+        .unwrap_or_default();
+    let module = examples::update_module(&module, &examples);
+
+    let operations = [
+        plan_module(module_path, module, options.force),
+        Ok(plan_input_file(input_path)),
+    ]
+    .into_iter()
+    .collect::<Result<Vec<_>, _>>()
+    .and_then(|mut operations| {
+        operations.extend(examples::plan_example_files(day, &examples, options.force)?);
+        operations.extend(plan_bench_target(day, options.force));
+        Ok(operations)
+    });
+
+    let mut operations = match operations {
+        Ok(operations) => operations,
         Err(e) => {
-            eprintln!("Failed to create example file: {e}");
+            eprintln!("Failed to scaffold day {day}: {e}");
             process::exit(1);
         }
+    };
+
+    operations.extend(plan_launch_config(day));
+
+    run_operations(&operations, options.dry_run);
+
+    if options.dry_run {
+        return;
     }
 
     println!("---");
     println!("🎄 Type `cargo solve {}` to run your solution.", day);
 }
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{
+        add_launch_configurations, parse_puzzle_title, render_template, write_bench_targets,
+        BENCH_TARGETS_COMMENT, MODULE_TEMPLATE,
+    };
+    use crate::day;
+
+    #[test]
+    fn renders_variables() {
+        let variables = [
+            ("day", "7".to_string()),
+            ("title", "Camel Cards".to_string()),
+        ];
+        let rendered = render_template("// Day {{day}}: {{ title }}\n{{day}}", &variables);
+        assert_eq!(rendered, "// Day 7: Camel Cards\n7");
+    }
+
+    #[test]
+    fn keeps_unknown_placeholders() {
+        let rendered = render_template("{{day}} {{month}}", &[("day", "7".to_string())]);
+        assert_eq!(rendered, "7 {{month}}");
+    }
+
+    #[test]
+    fn keeps_unterminated_placeholders() {
+        assert_eq!(
+            render_template("{{day", &[("day", "7".to_string())]),
+            "{{day"
+        );
+    }
+
+    #[test]
+    fn keeps_braces_in_code() {
+        let template = "let s = format!(\"{{}}\", {{day}});";
+        let rendered = render_template(template, &[("day", "7".to_string())]);
+        assert_eq!(rendered, "let s = format!(\"{{}}\", 7);");
+    }
+
+    #[test]
+    fn renders_builtin_template() {
+        let variables = [("day", "7".to_string()), ("answer_type", "u64".to_string())];
+        let rendered = render_template(MODULE_TEMPLATE, &variables);
+        assert!(rendered.starts_with("advent_of_code::solution!(7);"));
+        assert!(rendered.contains("pub fn part_one(input: &str) -> Option<u64> {"));
+    }
+
+    #[test]
+    fn parses_puzzle_title() {
+        let puzzle = "\\--- Day 7: Camel Cards ---\n----------\n\nYour all-expenses-paid trip...";
+        assert_eq!(parse_puzzle_title(puzzle).unwrap(), "Camel Cards");
+        assert_eq!(parse_puzzle_title("no title"), None);
+    }
+
+    #[test]
+    fn adds_launch_configurations() {
+        let launch = "{\n    // comment\n    \"configurations\": [\n        {\n            \"name\": \"existing\"\n        }\n    ]\n}\n";
+        let updated = add_launch_configurations(launch, day!(5)).unwrap();
+
+        assert!(updated.starts_with("{\n    // comment\n    \"configurations\": [\n        {\n            \"name\": \"existing\"\n        },\n        {\n"));
+        assert!(updated.contains("\"name\": \"Debug executable '05'\""));
+        assert!(updated.contains(
+            "\"args\": [\"test\", \"--no-run\", \"--bin=05\", \"--package=advent_of_code\"]"
+        ));
+        assert!(updated.ends_with("        }\n    ]\n}\n"));
+
+        assert_eq!(add_launch_configurations(&updated, day!(5)), None);
+        assert!(add_launch_configurations(&updated, day!(6)).is_some());
+    }
+
+    #[test]
+    fn adds_launch_configurations_to_empty_list() {
+        let updated = add_launch_configurations("{\"configurations\": []}", day!(5)).unwrap();
+        assert!(updated.starts_with("{\"configurations\": [\n        {\n"));
+        assert!(updated.ends_with("        }\n    ]}"));
+    }
+
+    #[test]
+    fn adds_launch_configurations_before_trailing_arrays() {
+        let launch = "{\n    \"configurations\": [\n        {\n            \"name\": \"a [b]\" // ]\n        }\n    ],\n    \"compounds\": [\n        { \"configurations\": [] }\n    ]\n}\n";
+        let updated = add_launch_configurations(launch, day!(5)).unwrap();
+
+        let (configurations, compounds) = updated.split_once("\"compounds\"").unwrap();
+        assert!(configurations.contains("\"name\": \"Debug executable '05'\""));
+        assert!(configurations.ends_with("        }\n    ],\n    "));
+        assert_eq!(
+            compounds,
+            ": [\n        { \"configurations\": [] }\n    ]\n}\n"
+        );
+    }
+
+    #[test]
+    fn writes_bench_targets() {
+        let manifest = "[package]\nname = \"advent_of_code\"\n\n[[bench]]\nname = \"05\"\nharness = false\n\n[[bench]]\nname = \"02\"\nharness = false\n\n[profile.profiling]\ndebug = true\n";
+
+        let updated = write_bench_targets(manifest, &[day!(5), day!(1)]).unwrap();
+        assert_eq!(
+            updated,
+            format!("[package]\nname = \"advent_of_code\"\n\n[profile.profiling]\ndebug = true\n\n{BENCH_TARGETS_COMMENT}\n[[bench]]\nname = \"01\"\nharness = false\n\n[[bench]]\nname = \"05\"\nharness = false\n")
+        );
+
+        assert_eq!(write_bench_targets(&updated, &[day!(1), day!(5)]), None);
+        assert_eq!(
+            write_bench_targets(&updated, &[]).unwrap(),
+            "[package]\nname = \"advent_of_code\"\n\n[profile.profiling]\ndebug = true\n"
+        );
+    }
+}
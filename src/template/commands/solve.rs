@@ -1,23 +1,159 @@
-use std::process::{Command, Stdio};
+use std::process::{self, Command, Stdio};
 
+#[cfg(feature = "profile")]
+use crate::template::commands::profile;
+use crate::template::commands::watch;
+use crate::template::environment;
+use crate::template::trace::Level;
 use crate::Day;
 
-pub fn handle(day: Day, release: bool, time: bool, submit_part: Option<u8>) {
+/// Options of the `solve` command, most of which are forwarded to the solution binary.
+#[derive(Default)]
+pub struct Options {
+    pub release: bool,
+    pub time: bool,
+    pub submit: Option<u8>,
+    pub trace: Option<Level>,
+    pub trace_file: Option<String>,
+    pub visualize: bool,
+    pub visualize_export: Option<String>,
+    pub render: Option<String>,
+    /// Path of an alternative input, `-` for stdin.
+    pub input: Option<String>,
+    /// Name of an input set in `data/inputs/<name>/`.
+    pub input_set: Option<String>,
+    /// Rerun the tests and the solution whenever a source or input of the day changes.
+    pub watch: bool,
+    /// Parameter overrides as `name=value`.
+    pub params: Vec<String>,
+    /// Build with the counting allocator of the `alloc_stats` feature.
+    pub memory: bool,
+    /// Profile this part and write a flamegraph instead of solving.
+    pub profile: Option<u8>,
+    /// Pin the thread that runs the parts to this core.
+    pub pin: Option<usize>,
+}
+
+pub fn handle(day: Day, options: Options) {
+    if options.input.is_some() && options.input_set.is_some() {
+        eprintln!("--input and --input-set can't be combined.");
+        process::exit(1);
+    }
+
+    if options.watch && options.input.as_deref() == Some("-") {
+        eprintln!("--watch can't read the input from stdin.");
+        process::exit(1);
+    }
+
+    if options.submit.is_some() && options.watch {
+        eprintln!("--submit can't be combined with --watch.");
+        process::exit(1);
+    }
+
+    if options.submit.is_some() && (options.input.is_some() || options.input_set.is_some()) {
+        eprintln!("Answers for alternative inputs can't be submitted.");
+        process::exit(1);
+    }
+
+    if options.submit.is_some() && !options.params.is_empty() {
+        eprintln!("Answers computed with --param overrides can't be submitted.");
+        process::exit(1);
+    }
+
+    if options.profile.is_some() && (options.submit.is_some() || options.watch) {
+        eprintln!("--profile can't be combined with --submit or --watch.");
+        process::exit(1);
+    }
+
     let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
 
-    if release {
+    if options.release {
         cmd_args.push("--release".to_string());
     }
 
+    if options.memory {
+        cmd_args.push("--features".to_string());
+        cmd_args.push("alloc_stats".to_string());
+    }
+
     cmd_args.push("--".to_string());
 
-    if let Some(submit_part) = submit_part {
+    if let Some(submit_part) = options.submit {
         cmd_args.push("--submit".to_string());
         cmd_args.push(submit_part.to_string());
     }
 
-    if time {
+    if options.time {
         cmd_args.push("--time".to_string());
+
+        for warning in environment::detect_warnings() {
+            eprintln!("⚠️ {warning}");
+        }
+    }
+
+    if let Some(pin) = options.pin {
+        cmd_args.push("--pin".to_string());
+        cmd_args.push(pin.to_string());
+    }
+
+    if let Some(trace) = options.trace {
+        cmd_args.push("--trace-level".to_string());
+        cmd_args.push(trace.to_string());
+    }
+
+    if let Some(trace_file) = options.trace_file {
+        cmd_args.push("--trace-file".to_string());
+        cmd_args.push(trace_file);
+    }
+
+    if options.visualize {
+        cmd_args.push("--visualize".to_string());
+    }
+
+    if let Some(visualize_export) = options.visualize_export {
+        cmd_args.push("--visualize-export".to_string());
+        cmd_args.push(visualize_export);
+    }
+
+    if let Some(render) = options.render {
+        cmd_args.push("--render".to_string());
+        cmd_args.push(render);
+    }
+
+    if let Some(input) = options.input {
+        cmd_args.push("--input".to_string());
+        cmd_args.push(input);
+    }
+
+    if let Some(input_set) = options.input_set {
+        cmd_args.push("--input-set".to_string());
+        cmd_args.push(input_set);
+    }
+
+    for param in options.params {
+        cmd_args.push("--param".to_string());
+        cmd_args.push(param);
+    }
+
+    if let Some(part) = options.profile {
+        #[cfg(feature = "profile")]
+        {
+            let separator = cmd_args.iter().position(|x| x == "--").unwrap_or_default();
+            profile::handle(day, part, &cmd_args[separator + 1..]);
+        }
+
+        #[cfg(not(feature = "profile"))]
+        {
+            let _ = part;
+            rerun_with_profile_feature();
+        }
+
+        return;
+    }
+
+    if options.watch {
+        watch::handle(day, &cmd_args);
+        return;
     }
 
     let mut cmd = Command::new("cargo")
@@ -29,3 +165,21 @@ pub fn handle(day: Day, release: bool, time: bool, submit_part: Option<u8>) {
 
     cmd.wait().unwrap();
 }
+
+/// Flamegraphs are rendered by `inferno`, which is only built with the `profile` feature. Without
+/// it, the command is run again by a build of this binary that has the feature.
+#[cfg(not(feature = "profile"))]
+fn rerun_with_profile_feature() {
+    let status = Command::new("cargo")
+        .args(["run", "--quiet", "--release", "--features", "profile", "--"])
+        .args(std::env::args().skip(1))
+        .status();
+
+    match status {
+        Ok(status) => process::exit(status.code().unwrap_or(1)),
+        Err(e) => {
+            eprintln!("Failed to build with the profile feature: {e}");
+            process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,13 @@
+use std::process;
+
+use crate::template::readme_stars;
+
+pub fn handle() {
+    match readme_stars::refresh() {
+        Ok(()) => println!("Successfully updated README with ⭐️ progress."),
+        Err(e) => {
+            eprintln!("Failed to update README with ⭐️ progress: {e}");
+            process::exit(1);
+        }
+    }
+}
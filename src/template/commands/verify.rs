@@ -0,0 +1,147 @@
+use std::path::{Path, PathBuf};
+use std::{fs, io, process};
+
+use crate::template::checksums::{self, Manifest, INPUTS_DIR};
+use crate::template::encryption;
+use crate::template::input;
+
+/// Checks all inputs in `data/inputs` against the checksum manifest. With `update`, the manifest is
+/// rewritten from the current inputs instead. With `encrypt`, plaintext inputs are replaced with
+/// their encrypted form.
+pub fn handle(update: bool, encrypt: bool) {
+    let inputs = match find_inputs(Path::new(INPUTS_DIR)) {
+        Ok(inputs) => inputs,
+        Err(e) => {
+            eprintln!("Failed to list inputs: {e}");
+            process::exit(1);
+        }
+    };
+
+    let manifest = match Manifest::load() {
+        Ok(manifest) => manifest,
+        Err(e) => {
+            eprintln!("Failed to read \"{}\": {e}", checksums::MANIFEST);
+            process::exit(1);
+        }
+    };
+
+    let key = match encryption::load_key() {
+        Ok(key) => key,
+        Err(e) => {
+            eprintln!("Failed to read key file: {e}");
+            process::exit(1);
+        }
+    };
+
+    if encrypt && key.is_none() {
+        eprintln!(
+            "Encrypting inputs requires a key. Set {} or add it to \"{}\".",
+            encryption::KEY_ENV,
+            encryption::KEY_FILE
+        );
+        process::exit(1);
+    }
+
+    let mut problems = 0;
+    let mut updated = Manifest::default();
+
+    for path in &inputs {
+        let name = checksums::manifest_name(path).unwrap_or_else(|| path.display().to_string());
+
+        let input = match input::read_plaintext(path) {
+            Ok(input) => input,
+            Err(e) => {
+                println!("✗ {name}: {e}");
+                problems += 1;
+                // keep the recorded checksum of inputs that can't be read, e.g. without a key.
+                if let Some(checksum) = manifest.entries.get(&name) {
+                    updated.entries.insert(name, checksum.clone());
+                }
+                continue;
+            }
+        };
+
+        updated.record(&name, input.as_bytes());
+
+        match manifest.entries.get(&name) {
+            None if update => println!("+ {name}: recorded checksum"),
+            None => println!("? {name}: no checksum in manifest"),
+            Some(_) => match manifest.verify(&name, input.as_bytes()) {
+                Ok(()) => println!("✓ {name}"),
+                Err(_) if update => println!("~ {name}: updated checksum"),
+                Err(_) => {
+                    println!("✗ {name}: does not match its checksum");
+                    problems += 1;
+                }
+            },
+        }
+
+        if let (true, Some(key)) = (encrypt && path.exists(), &key) {
+            let encrypted_path = encryption::encrypted_path(path);
+            let result = fs::write(&encrypted_path, key.encrypt(input.as_bytes()))
+                .and_then(|()| fs::remove_file(path));
+
+            match result {
+                Ok(()) => println!("  encrypted to \"{}\"", encrypted_path.display()),
+                Err(e) => {
+                    println!("  failed to encrypt: {e}");
+                    problems += 1;
+                }
+            }
+        }
+    }
+
+    for name in manifest.entries.keys() {
+        if !updated.entries.contains_key(name) {
+            if update {
+                println!("- {name}: removed from manifest");
+            } else {
+                println!("✗ {name}: missing");
+                problems += 1;
+            }
+        }
+    }
+
+    if update {
+        match updated.save() {
+            Ok(()) => println!("Updated \"{}\".", checksums::MANIFEST),
+            Err(e) => {
+                eprintln!("Failed to write \"{}\": {e}", checksums::MANIFEST);
+                process::exit(1);
+            }
+        }
+    }
+
+    if problems > 0 {
+        eprintln!("{problems} input(s) failed verification.");
+        process::exit(1);
+    }
+}
+
+/// Finds all inputs in `dir` and its subdirectories, by the path of their plaintext. Encrypted
+/// inputs `NN.txt.enc` are listed as `NN.txt`.
+fn find_inputs(dir: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut inputs = vec![];
+
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+
+        if path.is_dir() {
+            inputs.extend(find_inputs(&path)?);
+            continue;
+        }
+
+        let path = match path.extension().and_then(|x| x.to_str()) {
+            Some("txt") => path,
+            Some(encryption::EXTENSION) => path.with_extension(""),
+            _ => continue,
+        };
+
+        if path.extension().is_some_and(|x| x == "txt") && !inputs.contains(&path) {
+            inputs.push(path);
+        }
+    }
+
+    inputs.sort();
+    Ok(inputs)
+}
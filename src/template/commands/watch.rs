@@ -0,0 +1,238 @@
+/// Watch mode of the `solve` command: reruns the tests and the solution of a day whenever one of
+/// its sources or inputs changes. Files are polled, so this works the same on every platform.
+use std::collections::BTreeMap;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, SystemTime};
+use std::{fs, io};
+
+use crate::template::{runner, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use crate::Day;
+
+const POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Time without further changes before a rerun starts, so that a burst of saves triggers one run.
+const DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// Modification time and size of every watched file.
+type Snapshot = BTreeMap<PathBuf, (SystemTime, u64)>;
+
+/// Runs the tests and the solution, then reruns them on every change until interrupted.
+/// `run_args` are the arguments of the `cargo run` invocation of the solution.
+pub(super) fn handle(day: Day, run_args: &[String]) {
+    let mut previous: Option<[Option<String>; 2]> = None;
+
+    loop {
+        println!("{ANSI_BOLD}Testing day {day}{ANSI_RESET}");
+        let tests_passed = Command::new("cargo")
+            .args(["test", "--quiet", "--bin", &day.to_string()])
+            .status()
+            .is_ok_and(|status| status.success());
+
+        println!("{ANSI_BOLD}Solving day {day}{ANSI_RESET}");
+        match run_solution(run_args) {
+            Ok(answers) => {
+                print_diff(previous.as_ref(), &answers);
+                previous = Some(answers);
+            }
+            Err(e) => eprintln!("Failed to run the solution: {e}"),
+        }
+
+        if !tests_passed {
+            println!("{ANSI_BOLD}Tests failed.{ANSI_RESET}");
+        }
+
+        println!("{ANSI_ITALIC}Watching for changes, press Ctrl-C to stop.{ANSI_RESET}");
+        let changed = wait_for_changes(day);
+
+        println!();
+        println!("---");
+        for path in changed {
+            println!("Changed: {}", path.display());
+        }
+    }
+}
+
+/// Runs the solution, forwarding its output while capturing the answers. The output is forwarded
+/// as raw bytes as soon as it is written, so that progress lines that are redrawn with `\r` stay
+/// intact.
+fn run_solution(run_args: &[String]) -> io::Result<[Option<String>; 2]> {
+    let mut cmd = Command::new("cargo")
+        .args(run_args)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit())
+        .spawn()?;
+
+    let mut child_stdout = cmd.stdout.take().expect("stdout is piped");
+    let mut stdout = io::stdout();
+    let mut output = vec![];
+    let mut buffer = [0; 4096];
+
+    loop {
+        let n = match child_stdout.read(&mut buffer) {
+            Ok(0) => break,
+            Ok(n) => n,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        };
+
+        stdout.write_all(&buffer[..n])?;
+        stdout.flush()?;
+        output.extend_from_slice(&buffer[..n]);
+    }
+
+    cmd.wait()?;
+    Ok(runner::parse_answers(&String::from_utf8_lossy(&output)))
+}
+
+fn print_diff(previous: Option<&[Option<String>; 2]>, answers: &[Option<String>; 2]) {
+    let Some(previous) = previous else {
+        return;
+    };
+
+    let format = |answer: &Option<String>| answer.clone().unwrap_or_else(|| "✖".into());
+
+    println!("{ANSI_BOLD}Compared to the previous run:{ANSI_RESET}");
+    for (part, (before, after)) in previous.iter().zip(answers).enumerate() {
+        if before == after {
+            println!("  Part {}: unchanged", part + 1);
+        } else {
+            println!(
+                "  Part {}: {} → {ANSI_BOLD}{}{ANSI_RESET}",
+                part + 1,
+                format(before),
+                format(after)
+            );
+        }
+    }
+}
+
+/// Blocks until a watched file changed and no further changes happened for [`DEBOUNCE`].
+/// Returns the paths that changed.
+fn wait_for_changes(day: Day) -> Vec<PathBuf> {
+    let initial = snapshot(day);
+    let mut current = initial.clone();
+
+    while current == initial {
+        thread::sleep(POLL_INTERVAL);
+        current = snapshot(day);
+    }
+
+    loop {
+        thread::sleep(DEBOUNCE);
+        let next = snapshot(day);
+        if next == current {
+            break;
+        }
+        current = next;
+    }
+
+    changed_paths(&initial, &current)
+}
+
+fn changed_paths(before: &Snapshot, after: &Snapshot) -> Vec<PathBuf> {
+    let mut changed = after
+        .iter()
+        .filter(|(path, state)| before.get(*path) != Some(state))
+        .map(|(path, _)| path.clone())
+        .collect::<Vec<_>>();
+
+    changed.extend(
+        before
+            .keys()
+            .filter(|path| !after.contains_key(*path))
+            .cloned(),
+    );
+
+    changed.sort();
+    changed
+}
+
+fn snapshot(day: Day) -> Snapshot {
+    watched_files(day)
+        .into_iter()
+        .filter_map(|path| {
+            let metadata = fs::metadata(&path).ok()?;
+            Some((path, (metadata.modified().ok()?, metadata.len())))
+        })
+        .collect()
+}
+
+/// The solution of the day, the library, and the inputs and examples of the day, including named
+/// examples and input sets.
+fn watched_files(day: Day) -> Vec<PathBuf> {
+    let mut files = vec![PathBuf::from(format!("src/bin/{day}.rs"))];
+
+    files.extend(
+        list_files(Path::new("src"))
+            .into_iter()
+            .filter(|path| !path.starts_with("src/bin"))
+            .filter(|path| path.extension().is_some_and(|x| x == "rs")),
+    );
+
+    let prefix = day.to_string();
+    for dir in ["data/inputs", "data/examples"] {
+        files.extend(list_files(Path::new(dir)).into_iter().filter(|path| {
+            let in_day_dir = path
+                .strip_prefix(dir)
+                .is_ok_and(|relative| relative.starts_with(&prefix));
+            let day_file = path
+                .file_name()
+                .is_some_and(|name| name.to_string_lossy().starts_with(&prefix));
+            in_day_dir || day_file
+        }));
+    }
+
+    files
+}
+
+fn list_files(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return vec![];
+    };
+
+    entries
+        .filter_map(Result::ok)
+        .flat_map(|entry| {
+            let path = entry.path();
+            if path.is_dir() {
+                list_files(&path)
+            } else {
+                vec![path]
+            }
+        })
+        .collect()
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{changed_paths, Snapshot};
+    use std::path::PathBuf;
+    use std::time::{Duration, SystemTime};
+
+    #[test]
+    fn detects_changed_paths() {
+        let time = SystemTime::UNIX_EPOCH;
+        let later = time + Duration::from_secs(1);
+
+        let before: Snapshot = [
+            (PathBuf::from("a"), (time, 1)),
+            (PathBuf::from("b"), (time, 1)),
+            (PathBuf::from("c"), (time, 1)),
+        ]
+        .into();
+        let after: Snapshot = [
+            (PathBuf::from("a"), (time, 1)),
+            (PathBuf::from("b"), (later, 1)),
+            (PathBuf::from("d"), (time, 1)),
+        ]
+        .into();
+
+        assert_eq!(
+            changed_paths(&before, &after),
+            vec![PathBuf::from("b"), PathBuf::from("c"), PathBuf::from("d")]
+        );
+    }
+}
@@ -0,0 +1,85 @@
+/// Optional project configuration in `.aoc/config.toml`. Every setting has a default, so the file
+/// only needs to list the settings that differ.
+use std::{fs, io};
+
+use serde::Deserialize;
+
+use crate::template::reports::{Format, Target};
+
+pub const CONFIG_FILE: &str = ".aoc/config.toml";
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub readme: ReadmeConfig,
+    /// Benchmark reports, see [`Config::report_targets`].
+    pub reports: Vec<Target>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ReadmeConfig {
+    /// Show the timings of the last benchmark next to the stars, in the progress table, instead
+    /// of in a separate benchmarking table.
+    pub combine_stars_and_timings: bool,
+}
+
+impl Config {
+    /// Reads the configuration file. A missing file is treated as empty.
+    pub fn load() -> Result<Self, String> {
+        match fs::read_to_string(CONFIG_FILE) {
+            Ok(config) => Self::parse(&config),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(format!("could not read \"{CONFIG_FILE}\": {e}")),
+        }
+    }
+
+    /// The configured benchmark reports. Without configured reports, only the benchmarking table
+    /// of the readme is updated.
+    pub fn report_targets(&self) -> Vec<Target> {
+        if self.reports.is_empty() {
+            vec![Target::new(Format::Readme)]
+        } else {
+            self.reports.clone()
+        }
+    }
+
+    fn parse(config: &str) -> Result<Self, String> {
+        toml::from_str(config).map_err(|e| format!("invalid \"{CONFIG_FILE}\": {e}"))
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Config;
+    use crate::template::reports::{Format, Target};
+
+    #[test]
+    fn parses_config() {
+        let config = Config::parse("[readme]\ncombine_stars_and_timings = true\n").unwrap();
+        assert!(config.readme.combine_stars_and_timings);
+
+        assert_eq!(Config::parse("").unwrap(), Config::default());
+        assert!(Config::parse("[readme]\nunknown = 1\n").is_err());
+    }
+
+    #[test]
+    fn parses_report_targets() {
+        let config = Config::parse(
+            "[[reports]]\nformat = \"readme\"\nheading = \"### Release\"\n\n[[reports]]\nformat = \"csv\"\npath = \"bench.csv\"\n",
+        )
+        .unwrap();
+
+        let targets = config.report_targets();
+        assert_eq!(targets.len(), 2);
+        assert_eq!(targets[0].format, Format::Readme);
+        assert_eq!(targets[0].heading.as_deref(), Some("### Release"));
+        assert_eq!(targets[1].path(), "bench.csv");
+
+        assert_eq!(
+            Config::default().report_targets(),
+            vec![Target::new(Format::Readme)]
+        );
+        assert!(Config::parse("[[reports]]\nformat = \"pdf\"\n").is_err());
+    }
+}
@@ -0,0 +1,118 @@
+/// Optional encryption of puzzle inputs, so that they can be committed without publishing them.
+/// Encryption is enabled by providing a key, either via the `AOC_INPUT_KEY` environment variable
+/// or in the file `.aoc/input.key`. Encrypted inputs are stored next to where the plaintext would
+/// be, with an additional `.enc` extension, e.g. `data/inputs/01.txt.enc`.
+use std::path::{Path, PathBuf};
+use std::{env, fs, io};
+
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng};
+use chacha20poly1305::{ChaCha20Poly1305, Nonce};
+use sha2::{Digest, Sha256};
+
+pub const KEY_ENV: &str = "AOC_INPUT_KEY";
+pub const KEY_FILE: &str = ".aoc/input.key";
+
+/// Extension appended to the path of an encrypted file.
+pub const EXTENSION: &str = "enc";
+
+/// Identifies the format of an encrypted file. Followed by the nonce and the ciphertext.
+const MAGIC: &[u8] = b"AOCENC1\n";
+const NONCE_LEN: usize = 12;
+
+pub struct Key(ChaCha20Poly1305);
+
+impl Key {
+    /// Derives the key from a secret, e.g. the output of `openssl rand -hex 32`.
+    pub fn from_secret(secret: &str) -> Self {
+        let key = Sha256::digest(secret.trim().as_bytes());
+        Key(ChaCha20Poly1305::new(&key))
+    }
+
+    pub fn encrypt(&self, plaintext: &[u8]) -> Vec<u8> {
+        let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let ciphertext = self
+            .0
+            .encrypt(&nonce, plaintext)
+            .expect("encrypting in memory does not fail");
+
+        [MAGIC, nonce.as_slice(), &ciphertext].concat()
+    }
+
+    /// Decrypts a file written by [`Key::encrypt`]. Fails if the file was encrypted with another
+    /// key, or if it was modified or truncated.
+    pub fn decrypt(&self, data: &[u8]) -> Result<Vec<u8>, String> {
+        let data = data
+            .strip_prefix(MAGIC)
+            .ok_or_else(|| "not an encrypted input".to_string())?;
+
+        if data.len() < NONCE_LEN {
+            return Err("encrypted input is truncated".into());
+        }
+
+        let (nonce, ciphertext) = data.split_at(NONCE_LEN);
+        self.0
+            .decrypt(Nonce::from_slice(nonce), ciphertext)
+            .map_err(|_| "could not decrypt input: wrong key, or the file was modified".into())
+    }
+}
+
+/// Loads the key from the `AOC_INPUT_KEY` environment variable or the key file. Returns `None` if
+/// neither is set, i.e. encryption is disabled.
+pub fn load_key() -> io::Result<Option<Key>> {
+    if let Some(secret) = env::var(KEY_ENV).ok().filter(|x| !x.trim().is_empty()) {
+        return Ok(Some(Key::from_secret(&secret)));
+    }
+
+    match fs::read_to_string(KEY_FILE) {
+        Ok(secret) if !secret.trim().is_empty() => Ok(Some(Key::from_secret(&secret))),
+        Ok(_) => Ok(None),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/// Path of the encrypted form of `path`, e.g. `data/inputs/01.txt.enc`.
+pub fn encrypted_path(path: &Path) -> PathBuf {
+    let mut encrypted = path.as_os_str().to_owned();
+    encrypted.push(".");
+    encrypted.push(EXTENSION);
+    PathBuf::from(encrypted)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{encrypted_path, Key};
+    use std::path::{Path, PathBuf};
+
+    #[test]
+    fn encrypts_and_decrypts() {
+        let key = Key::from_secret("secret\n");
+        let encrypted = key.encrypt(b"1abc2\n");
+
+        assert_ne!(&encrypted[encrypted.len() - 6..], b"1abc2\n");
+        assert_eq!(key.decrypt(&encrypted).unwrap(), b"1abc2\n");
+        assert_eq!(
+            Key::from_secret("secret").decrypt(&encrypted).unwrap(),
+            b"1abc2\n"
+        );
+    }
+
+    #[test]
+    fn rejects_wrong_keys_and_modified_files() {
+        let encrypted = Key::from_secret("secret").encrypt(b"1abc2\n");
+
+        assert!(Key::from_secret("other").decrypt(&encrypted).is_err());
+        assert!(Key::from_secret("secret")
+            .decrypt(&encrypted[..encrypted.len() - 1])
+            .is_err());
+        assert!(Key::from_secret("secret").decrypt(b"1abc2\n").is_err());
+    }
+
+    #[test]
+    fn appends_extension() {
+        assert_eq!(
+            encrypted_path(Path::new("data/inputs/01.txt")),
+            PathBuf::from("data/inputs/01.txt.enc")
+        );
+    }
+}
@@ -0,0 +1,381 @@
+/// Environment of a benchmark run. Timings are only comparable on the same machine and build, so
+/// `cargo all --release --time` records the CPU, the compiler, the build settings and the git
+/// revision next to the timings, and warns about conditions that make timings unreliable.
+///
+/// The benchmark thread of the solutions can be pinned to one core with `--pin <core>`, see
+/// [`init_from_args`].
+use std::fmt::Display;
+use std::process::{self, Command};
+use std::{env, fs};
+
+use serde::Serialize;
+
+/// Load average per core above which the system counts as busy.
+const MAX_LOAD_PER_CORE: f64 = 0.5;
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct Environment {
+    pub cpu: Option<String>,
+    /// Logical cores.
+    pub cores: usize,
+    /// Output of `rustc --version`.
+    pub rustc: Option<String>,
+    /// `target-cpu` of the rustflags, if set.
+    pub target_cpu: Option<String>,
+    pub opt_level: String,
+    /// Short hash of `HEAD`, suffixed with `-dirty` if tracked files were changed.
+    pub git_revision: Option<String>,
+    pub pinned_core: Option<usize>,
+    /// Conditions that make the timings unreliable, see [`check`].
+    pub warnings: Vec<String>,
+}
+
+impl Environment {
+    /// Captures the environment of the current machine and project.
+    pub fn capture(is_release: bool, pinned_core: Option<usize>) -> Self {
+        Self {
+            cpu: read_cpu_model(),
+            cores: read_cores(),
+            rustc: command_output(
+                &env::var("RUSTC").unwrap_or_else(|_| "rustc".into()),
+                &["--version"],
+            ),
+            target_cpu: find_target_cpu(&read_rustflags()),
+            opt_level: read_opt_level(is_release),
+            git_revision: read_git_revision(),
+            pinned_core,
+            warnings: detect_warnings(),
+        }
+    }
+}
+
+/// Checks the current machine for conditions that make timings unreliable.
+pub fn detect_warnings() -> Vec<String> {
+    check(&read_governors(), read_load(), read_cores())
+}
+
+impl Display for Environment {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut fields = vec![format!(
+            "{} ({})",
+            self.cpu.as_deref().unwrap_or("unknown CPU"),
+            format_cores(self.cores)
+        )];
+
+        fields.extend(self.rustc.clone());
+
+        if let Some(target_cpu) = &self.target_cpu {
+            fields.push(format!("target-cpu={target_cpu}"));
+        }
+
+        fields.push(format!("opt-level={}", self.opt_level));
+
+        if let Some(revision) = &self.git_revision {
+            fields.push(format!("git {revision}"));
+        }
+
+        if let Some(core) = self.pinned_core {
+            fields.push(format!("pinned to core {core}"));
+        }
+
+        write!(f, "{}", fields.join(" · "))
+    }
+}
+
+/// Warns about a CPU frequency governor other than `performance`, which scales the clock with
+/// the load, and about a high system load.
+pub fn check(governors: &[String], load: Option<f64>, cores: usize) -> Vec<String> {
+    let mut warnings = vec![];
+
+    let mut scaling = governors
+        .iter()
+        .filter(|x| x.as_str() != "performance")
+        .collect::<Vec<_>>();
+    scaling.sort();
+    scaling.dedup();
+
+    if !scaling.is_empty() {
+        let scaling = scaling
+            .iter()
+            .map(|x| format!("\"{x}\""))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        warnings.push(format!(
+            "CPU frequency scaling is active (governor {scaling}), use the \"performance\" governor for stable timings."
+        ));
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    let max_load = cores as f64 * MAX_LOAD_PER_CORE;
+
+    if let Some(load) = load.filter(|x| *x > max_load) {
+        warnings.push(format!(
+            "High system load ({load:.2} on {}), timings may be slower than usual.",
+            format_cores(cores)
+        ));
+    }
+
+    warnings
+}
+
+fn format_cores(cores: usize) -> String {
+    match cores {
+        1 => "1 core".into(),
+        _ => format!("{cores} cores"),
+    }
+}
+
+fn read_cores() -> usize {
+    std::thread::available_parallelism().map_or(1, usize::from)
+}
+
+fn command_output(program: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(program).args(args).output().ok()?;
+    let output = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (!output.is_empty()).then_some(output)
+}
+
+fn read_cpu_model() -> Option<String> {
+    fs::read_to_string("/proc/cpuinfo")
+        .ok()
+        .and_then(|cpuinfo| parse_cpu_model(&cpuinfo))
+        .or_else(|| command_output("sysctl", &["-n", "machdep.cpu.brand_string"]))
+}
+
+/// Reads the CPU model from `/proc/cpuinfo`. x86 lists a `model name`, ARM boards a `Model` or
+/// `Hardware`.
+fn parse_cpu_model(cpuinfo: &str) -> Option<String> {
+    ["model name", "Model", "Hardware"].iter().find_map(|key| {
+        cpuinfo.lines().find_map(|line| {
+            let (name, value) = line.split_once(':')?;
+            (name.trim() == *key && !value.trim().is_empty()).then(|| value.trim().to_string())
+        })
+    })
+}
+
+/// Governors of all cores, empty if frequency scaling is not supported or not visible.
+fn read_governors() -> Vec<String> {
+    let Ok(cpus) = fs::read_dir("/sys/devices/system/cpu") else {
+        return vec![];
+    };
+
+    cpus.filter_map(Result::ok)
+        .filter(|x| {
+            x.file_name()
+                .to_str()
+                .and_then(|name| name.strip_prefix("cpu"))
+                .is_some_and(|id| !id.is_empty() && id.chars().all(|c| c.is_ascii_digit()))
+        })
+        .filter_map(|x| fs::read_to_string(x.path().join("cpufreq/scaling_governor")).ok())
+        .map(|x| x.trim().to_string())
+        .collect()
+}
+
+/// Load average of the last minute.
+fn read_load() -> Option<f64> {
+    let load = fs::read_to_string("/proc/loadavg")
+        .ok()
+        .or_else(|| command_output("sysctl", &["-n", "vm.loadavg"]))?;
+
+    load.trim_start_matches(['{', ' '])
+        .split_whitespace()
+        .next()?
+        .parse()
+        .ok()
+}
+
+/// Rustflags from the environment and `.cargo/config.toml`.
+fn read_rustflags() -> String {
+    let mut flags = vec![];
+
+    flags.extend(env::var("CARGO_ENCODED_RUSTFLAGS").map(|x| x.replace('\x1f', " ")));
+    flags.extend(env::var("RUSTFLAGS"));
+    flags.extend(env::var("CARGO_BUILD_RUSTFLAGS"));
+
+    if let Some(config) = fs::read_to_string(".cargo/config.toml")
+        .ok()
+        .and_then(|x| x.parse::<toml::Table>().ok())
+    {
+        match config.get("build").and_then(|x| x.get("rustflags")) {
+            Some(toml::Value::String(x)) => flags.push(x.clone()),
+            Some(toml::Value::Array(x)) => {
+                flags.extend(x.iter().filter_map(|x| x.as_str().map(String::from)));
+            }
+            _ => {}
+        }
+    }
+
+    flags.join(" ")
+}
+
+/// Finds `-C target-cpu=<cpu>` in rustflags. The last occurrence wins, like in rustc.
+fn find_target_cpu(flags: &str) -> Option<String> {
+    flags
+        .split_whitespace()
+        .filter_map(|x| x.trim_start_matches("-C").strip_prefix("target-cpu="))
+        .next_back()
+        .map(String::from)
+}
+
+fn read_opt_level(is_release: bool) -> String {
+    let profile = if is_release { "release" } else { "dev" };
+
+    env::var(format!(
+        "CARGO_PROFILE_{}_OPT_LEVEL",
+        profile.to_ascii_uppercase()
+    ))
+    .ok()
+    .or_else(|| {
+        fs::read_to_string("Cargo.toml")
+            .ok()
+            .and_then(|manifest| parse_opt_level(&manifest, profile))
+    })
+    .unwrap_or_else(|| if is_release { "3" } else { "0" }.into())
+}
+
+/// Reads the `opt-level` of a profile from the manifest.
+fn parse_opt_level(manifest: &str, profile: &str) -> Option<String> {
+    let manifest = manifest.parse::<toml::Table>().ok()?;
+
+    match manifest.get("profile")?.get(profile)?.get("opt-level")? {
+        toml::Value::Integer(x) => Some(x.to_string()),
+        toml::Value::String(x) => Some(x.clone()),
+        _ => None,
+    }
+}
+
+fn read_git_revision() -> Option<String> {
+    let revision = command_output("git", &["rev-parse", "--short", "HEAD"])?;
+
+    let is_dirty = Command::new("git")
+        .args(["status", "--porcelain", "--untracked-files=no"])
+        .output()
+        .is_ok_and(|x| !x.stdout.is_empty());
+
+    Some(if is_dirty {
+        format!("{revision}-dirty")
+    } else {
+        revision
+    })
+}
+
+/// Pins the current thread to `core`.
+#[cfg(target_os = "linux")]
+pub fn pin(core: usize) -> Result<(), String> {
+    if core >= libc::CPU_SETSIZE as usize {
+        return Err(format!("core {core} does not exist"));
+    }
+
+    // SAFETY: the set is a plain bitmask that is initialised by zeroing, and `core` was checked to
+    // be within its bounds.
+    let result = unsafe {
+        let mut set: libc::cpu_set_t = std::mem::zeroed();
+        libc::CPU_SET(core, &mut set);
+        libc::sched_setaffinity(0, std::mem::size_of::<libc::cpu_set_t>(), &set)
+    };
+
+    if result == 0 {
+        Ok(())
+    } else {
+        Err(std::io::Error::last_os_error().to_string())
+    }
+}
+
+/// Pins the current thread to `core`.
+#[cfg(not(target_os = "linux"))]
+pub fn pin(_: usize) -> Result<(), String> {
+    Err("pinning threads is only supported on Linux".into())
+}
+
+/// Pins the thread that runs the parts if the solution binary was invoked with `--pin <core>`.
+/// Called by the `solution!` macro before running the parts.
+pub fn init_from_args() {
+    let args: Vec<String> = env::args().collect();
+    let Some(index) = args.iter().position(|x| x == "--pin") else {
+        return;
+    };
+
+    let Some(core) = args.get(index + 1).and_then(|x| x.parse().ok()) else {
+        eprintln!("Unexpected command-line input. Format: cargo solve 1 --time --pin 2");
+        process::exit(1);
+    };
+
+    if let Err(e) = pin(core) {
+        eprintln!("Failed to pin to core {core}, timings are not isolated: {e}");
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{check, find_target_cpu, parse_cpu_model, parse_opt_level, Environment};
+
+    #[test]
+    fn parses_cpu_model() {
+        let cpuinfo = "processor\t: 0\nvendor_id\t: AuthenticAMD\nmodel name\t: AMD Ryzen 7 5800X 8-Core Processor\n";
+        assert_eq!(
+            parse_cpu_model(cpuinfo).unwrap(),
+            "AMD Ryzen 7 5800X 8-Core Processor"
+        );
+        assert_eq!(
+            parse_cpu_model("processor\t: 0\nModel\t\t: Raspberry Pi 4 Model B Rev 1.4\n").unwrap(),
+            "Raspberry Pi 4 Model B Rev 1.4"
+        );
+        assert_eq!(parse_cpu_model("processor\t: 0\n"), None);
+    }
+
+    #[test]
+    fn finds_target_cpu() {
+        assert_eq!(find_target_cpu("-C target-cpu=native").unwrap(), "native");
+        assert_eq!(
+            find_target_cpu("-Ctarget-cpu=znver3 -C opt-level=3 -C target-cpu=skylake").unwrap(),
+            "skylake"
+        );
+        assert_eq!(find_target_cpu("-C opt-level=3"), None);
+    }
+
+    #[test]
+    fn parses_opt_level() {
+        let manifest = "[profile.release]\nopt-level = 2\n\n[profile.dev]\nopt-level = \"s\"\n";
+        assert_eq!(parse_opt_level(manifest, "release").unwrap(), "2");
+        assert_eq!(parse_opt_level(manifest, "dev").unwrap(), "s");
+        assert_eq!(
+            parse_opt_level("[package]\nname = \"x\"\n", "release"),
+            None
+        );
+    }
+
+    #[test]
+    fn warns_about_unstable_conditions() {
+        let governors = ["performance".to_string(), "powersave".to_string()];
+        assert_eq!(
+            check(&governors, Some(6.5), 8),
+            [
+                "CPU frequency scaling is active (governor \"powersave\"), use the \"performance\" governor for stable timings.",
+                "High system load (6.50 on 8 cores), timings may be slower than usual.",
+            ]
+        );
+
+        assert!(check(&["performance".to_string()], Some(1.0), 8).is_empty());
+        assert!(check(&[], None, 8).is_empty());
+    }
+
+    #[test]
+    fn formats_environment() {
+        let environment = Environment {
+            cpu: Some("AMD Ryzen 7 5800X".into()),
+            cores: 16,
+            rustc: Some("rustc 1.75.0".into()),
+            target_cpu: Some("native".into()),
+            opt_level: "3".into(),
+            git_revision: Some("1a2b3c4".into()),
+            pinned_core: Some(2),
+            warnings: vec![],
+        };
+
+        assert_eq!(
+            environment.to_string(),
+            "AMD Ryzen 7 5800X (16 cores) · rustc 1.75.0 · target-cpu=native · opt-level=3 · git 1a2b3c4 · pinned to core 2"
+        );
+    }
+}
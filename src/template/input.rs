@@ -0,0 +1,235 @@
+/// Selects the input a solution is run against.
+/// By default, solutions read `data/inputs/NN.txt`. The solution binary can be pointed to another
+/// input via `--input <path>`, `--input -` for stdin, or `--input-set <name>` for
+/// `data/inputs/<name>/NN.txt`, e.g. through `cargo solve 1 --input-set alice`.
+use std::fmt::Display;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::{env, fs, process};
+
+use crate::template::checksums::{self, Manifest};
+use crate::template::{encryption, read_file};
+use crate::Day;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+    /// `data/inputs/NN.txt`.
+    Default,
+    Stdin,
+    File(PathBuf),
+    /// A named input set, `data/inputs/<name>/NN.txt`.
+    Set(String),
+}
+
+impl Source {
+    /// Determines the source from the arguments of the solution binary.
+    pub fn from_args(args: &[String]) -> Result<Self, String> {
+        let value = |flag: &str| -> Result<Option<&String>, String> {
+            match args.iter().position(|x| x == flag) {
+                Some(i) => args
+                    .get(i + 1)
+                    .map(Some)
+                    .ok_or_else(|| format!("{flag} expects a value")),
+                None => Ok(None),
+            }
+        };
+
+        match (value("--input")?, value("--input-set")?) {
+            (Some(_), Some(_)) => Err("--input and --input-set can't be combined".into()),
+            (Some(path), None) if path == "-" => Ok(Source::Stdin),
+            (Some(path), None) => Ok(Source::File(path.into())),
+            (None, Some(name)) => Ok(Source::Set(name.clone())),
+            (None, None) => Ok(Source::Default),
+        }
+    }
+
+    /// The file the input is read from, `None` for stdin.
+    pub fn path(&self, day: Day) -> Option<PathBuf> {
+        match self {
+            Source::Default => Some(PathBuf::from(format!("data/inputs/{day}.txt"))),
+            Source::Stdin => None,
+            Source::File(path) => Some(path.clone()),
+            Source::Set(name) => Some(
+                PathBuf::from("data/inputs")
+                    .join(name)
+                    .join(format!("{day}.txt")),
+            ),
+        }
+    }
+
+    pub fn read(&self, day: Day) -> Result<String, String> {
+        match self {
+            Source::Default => Ok(read_file("inputs", day)),
+            Source::Stdin => {
+                let mut input = String::new();
+                io::stdin()
+                    .read_to_string(&mut input)
+                    .map_err(|e| e.to_string())?;
+                Ok(input)
+            }
+            Source::File(_) | Source::Set(_) => read_input_file(&self.path(day).unwrap()),
+        }
+    }
+}
+
+impl Display for Source {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Source::Default => write!(f, "default input"),
+            Source::Stdin => write!(f, "stdin"),
+            Source::File(path) => write!(f, "\"{}\"", path.display()),
+            Source::Set(name) => write!(f, "input set \"{name}\""),
+        }
+    }
+}
+
+/// Reads the plaintext of an input file. If the encrypted form `<path>.enc` exists and the
+/// plaintext is missing or empty, e.g. because it was created by `scaffold`, the encrypted form is
+/// decrypted with the key from [`encryption::load_key`].
+pub fn read_plaintext(path: &Path) -> Result<String, String> {
+    let encrypted_path = encryption::encrypted_path(path);
+    let has_plaintext = fs::metadata(path).is_ok_and(|metadata| metadata.len() > 0);
+
+    if has_plaintext || !encrypted_path.exists() {
+        return fs::read_to_string(path).map_err(|e| e.to_string());
+    }
+
+    let key = encryption::load_key()
+        .map_err(|e| format!("could not read key file: {e}"))?
+        .ok_or_else(|| {
+            format!(
+                "\"{}\" is encrypted. Set {} or add the key to \"{}\".",
+                encrypted_path.display(),
+                encryption::KEY_ENV,
+                encryption::KEY_FILE
+            )
+        })?;
+
+    let data = fs::read(&encrypted_path).map_err(|e| e.to_string())?;
+    let plaintext = key.decrypt(&data)?;
+    String::from_utf8(plaintext).map_err(|e| e.to_string())
+}
+
+/// Reads an input file like [`read_plaintext`] and checks it against the checksum manifest.
+pub fn read_input_file(path: &Path) -> Result<String, String> {
+    let input = read_plaintext(path)?;
+
+    if let Some(name) = checksums::manifest_name(path) {
+        let manifest = Manifest::load().map_err(|e| e.to_string())?;
+        manifest.verify(&name, input.as_bytes())?;
+    }
+
+    Ok(input)
+}
+
+/// Records the checksum of a freshly downloaded input and, if encryption is enabled, replaces the
+/// plaintext with its encrypted form. Returns the path the input is stored at.
+pub fn store_downloaded(path: &Path) -> Result<PathBuf, String> {
+    let input = fs::read(path).map_err(|e| e.to_string())?;
+
+    if let Some(name) = checksums::manifest_name(path) {
+        let mut manifest = Manifest::load().map_err(|e| e.to_string())?;
+        manifest.record(&name, &input);
+        manifest.save().map_err(|e| e.to_string())?;
+    }
+
+    let Some(key) = encryption::load_key().map_err(|e| e.to_string())? else {
+        return Ok(path.to_path_buf());
+    };
+
+    let encrypted_path = encryption::encrypted_path(path);
+    fs::write(&encrypted_path, key.encrypt(&input)).map_err(|e| e.to_string())?;
+    fs::remove_file(path).map_err(|e| e.to_string())?;
+    Ok(encrypted_path)
+}
+
+/// Lists the names of all input sets, i.e. the directories in `data/inputs/`.
+pub fn input_sets() -> io::Result<Vec<String>> {
+    let mut sets = fs::read_dir("data/inputs")?
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_type().is_ok_and(|x| x.is_dir()))
+        .map(|entry| entry.file_name().to_string_lossy().to_string())
+        .collect::<Vec<_>>();
+
+    sets.sort();
+    Ok(sets)
+}
+
+/// Reads the input selected by the arguments of the solution binary. Called by the `solution!`
+/// macro. The input is announced on stderr, so it's clear which input the answers are for.
+pub fn read_from_args(day: Day) -> String {
+    let args: Vec<String> = env::args().collect();
+
+    let source = match Source::from_args(&args) {
+        Ok(source) => source,
+        Err(e) => {
+            eprintln!("Invalid input: {e}.");
+            process::exit(1);
+        }
+    };
+
+    let input = match source.read(day) {
+        Ok(input) => input,
+        Err(e) => {
+            eprintln!("Could not read {source}: {e}");
+            process::exit(1);
+        }
+    };
+
+    match &source {
+        Source::Default | Source::Set(_) => eprintln!(
+            "Using {source} (\"{}\").",
+            source.path(day).unwrap().display()
+        ),
+        _ => eprintln!("Using input from {source}."),
+    }
+
+    input
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Source;
+    use crate::day;
+    use std::path::PathBuf;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn parses_sources() {
+        assert_eq!(Source::from_args(&args(&["01"])), Ok(Source::Default));
+        assert_eq!(
+            Source::from_args(&args(&["01", "--input", "-"])),
+            Ok(Source::Stdin)
+        );
+        assert_eq!(
+            Source::from_args(&args(&["01", "--input", "edge.txt", "--time"])),
+            Ok(Source::File("edge.txt".into()))
+        );
+        assert_eq!(
+            Source::from_args(&args(&["01", "--input-set", "alice"])),
+            Ok(Source::Set("alice".into()))
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_arguments() {
+        assert!(Source::from_args(&args(&["01", "--input"])).is_err());
+        assert!(Source::from_args(&args(&["01", "--input", "a", "--input-set", "b"])).is_err());
+    }
+
+    #[test]
+    fn resolves_paths() {
+        assert_eq!(
+            Source::Set("alice".into()).path(day!(5)),
+            Some(PathBuf::from("data/inputs/alice/05.txt"))
+        );
+        assert_eq!(
+            Source::Default.path(day!(5)),
+            Some(PathBuf::from("data/inputs/05.txt"))
+        );
+        assert_eq!(Source::Stdin.path(day!(5)), None);
+    }
+}
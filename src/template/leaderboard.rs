@@ -0,0 +1,428 @@
+/// Parses and renders the JSON API of private leaderboards,
+/// `https://adventofcode.com/<year>/leaderboard/private/view/<id>.json`.
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write as _;
+use std::str::FromStr;
+
+use serde::Deserialize;
+
+use crate::template::{ANSI_BOLD, ANSI_RESET};
+use crate::{all_days, Day};
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Leaderboard {
+    pub event: String,
+    pub owner_id: u64,
+    pub members: HashMap<String, Member>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Member {
+    pub id: u64,
+    /// Anonymous members don't have a name.
+    pub name: Option<String>,
+    pub stars: u32,
+    pub local_score: u64,
+    pub global_score: u64,
+    /// Unix time of the last star, `0` if the member has no stars.
+    pub last_star_ts: u64,
+    /// Stars by day and part, e.g. `{"1": {"1": {..}, "2": {..}}}`.
+    pub completion_day_level: BTreeMap<String, BTreeMap<String, Star>>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Star {
+    /// Unix time the star was earned.
+    pub get_star_ts: u64,
+}
+
+impl Leaderboard {
+    pub fn parse(json: &str) -> Result<Self, String> {
+        serde_json::from_str(json).map_err(|e| format!("invalid leaderboard: {e}"))
+    }
+
+    pub fn year(&self) -> Option<i64> {
+        self.event.parse().ok()
+    }
+
+    /// Seconds between the unlock of `day`, midnight EST, and `timestamp`.
+    pub fn since_unlock(&self, day: Day, timestamp: u64) -> Option<u64> {
+        let unlock = unlock_time(self.year()?, day);
+        timestamp.checked_sub(unlock)
+    }
+
+    /// Members ordered by `sort`. Sorting by a part or by the delta between parts requires `day`.
+    pub fn ranking(&self, sort: SortBy, day: Option<Day>) -> Result<Vec<&Member>, String> {
+        let mut members = self.members.values().collect::<Vec<_>>();
+
+        // ties are ordered by name, then id, so the ranking is stable.
+        members.sort_by(|a, b| {
+            a.display_name()
+                .cmp(&b.display_name())
+                .then(a.id.cmp(&b.id))
+        });
+
+        match (sort, day) {
+            (SortBy::Score, _) => members.sort_by_key(|x| Reverse(x.local_score)),
+            (SortBy::Stars, _) => members.sort_by(|a, b| {
+                b.stars
+                    .cmp(&a.stars)
+                    .then(a.last_star_ts.cmp(&b.last_star_ts))
+            }),
+            (SortBy::Name, _) => {}
+            (SortBy::Last, _) => members.sort_by_key(|x| Reverse(x.last_star_ts)),
+            (SortBy::Part1 | SortBy::Part2 | SortBy::Delta, Some(day)) => {
+                // members without the star go last.
+                members.sort_by_key(|member| {
+                    let value = match sort {
+                        SortBy::Part1 => member.star(day, 1),
+                        SortBy::Part2 => member.star(day, 2),
+                        _ => member.delta(day),
+                    };
+                    (value.is_none(), value)
+                });
+            }
+            (sort, None) => return Err(format!("sorting by {sort} requires --day")),
+        }
+
+        Ok(members)
+    }
+
+    /// Ranking with one column per day: `★` for both stars, `☆` for part one only.
+    pub fn render_overview(&self, sort: SortBy) -> Result<String, String> {
+        let members = self.ranking(sort, None)?;
+        let width = name_width(&members);
+        let mut output = self.render_title();
+
+        let tens = all_days()
+            .map(|day| match day.into_inner() / 10 {
+                0 => ' ',
+                digit => char::from(b'0' + digit),
+            })
+            .collect::<String>();
+        let ones = all_days()
+            .map(|day| char::from(b'0' + day.into_inner() % 10))
+            .collect::<String>();
+
+        let _ = writeln!(
+            output,
+            "{:>4}  {:width$}  {:>5}  {:>2}  {tens}",
+            "", "", "", ""
+        );
+        let _ = writeln!(
+            output,
+            "{ANSI_BOLD}{:>4}  {:width$}  {:>5}  {:>2}  {ones}{ANSI_RESET}",
+            "#", "Name", "Score", "★"
+        );
+
+        for (rank, member) in members.iter().enumerate() {
+            let stars = all_days()
+                .map(|day| match (member.star(day, 1), member.star(day, 2)) {
+                    (Some(_), Some(_)) => '★',
+                    (Some(_), None) => '☆',
+                    _ => '·',
+                })
+                .collect::<String>();
+
+            let _ = writeln!(
+                output,
+                "{:>3})  {:width$}  {:>5}  {:>2}  {stars}",
+                rank + 1,
+                member.display_name(),
+                member.local_score,
+                member.stars,
+            );
+        }
+
+        let stars = self.members.values().map(|x| x.stars).sum::<u32>();
+        let _ = write!(
+            output,
+            "\n{} members, {stars} stars in total",
+            self.members.len()
+        );
+        if !self.members.is_empty() {
+            let _ = write!(
+                output,
+                ", {:.1} per member",
+                f64::from(stars) / self.members.len() as f64
+            );
+        }
+        output.push('\n');
+
+        Ok(output)
+    }
+
+    /// Times of both stars of one day, relative to the unlock of the day, and the delta between
+    /// them.
+    pub fn render_day(&self, day: Day, sort: SortBy) -> Result<String, String> {
+        let members = self.ranking(sort, Some(day))?;
+        let mut output = self.render_title();
+
+        let solved = |part| {
+            members
+                .iter()
+                .filter(|member| member.star(day, part).is_some())
+                .count()
+        };
+        let _ = writeln!(
+            output,
+            "Day {day}: {} solved part one, {} solved part two.\n",
+            solved(1),
+            solved(2)
+        );
+
+        let time = |timestamp: Option<u64>| {
+            timestamp
+                .and_then(|timestamp| self.since_unlock(day, timestamp))
+                .map_or_else(|| "-".into(), format_duration)
+        };
+
+        let members = members
+            .into_iter()
+            .filter(|member| member.star(day, 1).is_some())
+            .collect::<Vec<_>>();
+        let width = name_width(&members);
+
+        let _ = writeln!(
+            output,
+            "{ANSI_BOLD}{:>4}  {:width$}  {:>10}  {:>10}  {:>10}{ANSI_RESET}",
+            "#", "Name", "Part 1", "Part 2", "Delta"
+        );
+
+        for (rank, member) in members.iter().enumerate() {
+            let _ = writeln!(
+                output,
+                "{:>3})  {:width$}  {:>10}  {:>10}  {:>10}",
+                rank + 1,
+                member.display_name(),
+                time(member.star(day, 1)),
+                time(member.star(day, 2)),
+                member
+                    .delta(day)
+                    .map_or_else(|| "-".into(), format_duration),
+            );
+        }
+
+        if let Some(fastest) = members
+            .iter()
+            .filter_map(|member| Some((member.delta(day)?, member)))
+            .min_by_key(|(delta, _)| *delta)
+        {
+            let _ = writeln!(
+                output,
+                "\nFastest part two: {} ({}).",
+                fastest.1.display_name(),
+                format_duration(fastest.0)
+            );
+        }
+
+        Ok(output)
+    }
+
+    fn render_title(&self) -> String {
+        format!(
+            "{ANSI_BOLD}Private leaderboard {} · {}{ANSI_RESET}\n\n",
+            self.owner_id, self.event
+        )
+    }
+}
+
+impl Member {
+    pub fn display_name(&self) -> String {
+        self.name
+            .clone()
+            .unwrap_or_else(|| format!("(anonymous user #{})", self.id))
+    }
+
+    /// Unix time the star of `part` of `day` was earned.
+    pub fn star(&self, day: Day, part: u8) -> Option<u64> {
+        self.completion_day_level
+            .get(&day.into_inner().to_string())?
+            .get(&part.to_string())
+            .map(|star| star.get_star_ts)
+    }
+
+    /// Seconds between the two stars of `day`.
+    pub fn delta(&self, day: Day) -> Option<u64> {
+        self.star(day, 2)?.checked_sub(self.star(day, 1)?)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SortBy {
+    /// Local score, as on the website.
+    #[default]
+    Score,
+    Stars,
+    Name,
+    /// Most recent star first.
+    Last,
+    Part1,
+    Part2,
+    Delta,
+}
+
+impl FromStr for SortBy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "score" => Ok(Self::Score),
+            "stars" => Ok(Self::Stars),
+            "name" => Ok(Self::Name),
+            "last" => Ok(Self::Last),
+            "part1" => Ok(Self::Part1),
+            "part2" => Ok(Self::Part2),
+            "delta" => Ok(Self::Delta),
+            _ => Err(format!(
+                "unknown sort order \"{s}\", expected one of score, stars, name, last, part1, part2, delta"
+            )),
+        }
+    }
+}
+
+impl std::fmt::Display for SortBy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Self::Score => "score",
+            Self::Stars => "stars",
+            Self::Name => "name",
+            Self::Last => "last",
+            Self::Part1 => "part1",
+            Self::Part2 => "part2",
+            Self::Delta => "delta",
+        };
+        write!(f, "{name}")
+    }
+}
+
+fn name_width(members: &[&Member]) -> usize {
+    members
+        .iter()
+        .map(|member| member.display_name().chars().count())
+        .max()
+        .unwrap_or(0)
+        .max(4)
+}
+
+/// Formats seconds as `hh:mm:ss`, prefixed with the number of days if longer than a day.
+fn format_duration(seconds: u64) -> String {
+    let (days, seconds) = (seconds / 86_400, seconds % 86_400);
+    let time = format!(
+        "{:02}:{:02}:{:02}",
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60
+    );
+
+    if days > 0 {
+        format!("{days}d {time}")
+    } else {
+        time
+    }
+}
+
+/// Unix time at which `day` of `year` unlocks: midnight EST, i.e. 05:00 UTC on December `day`.
+fn unlock_time(year: i64, day: Day) -> u64 {
+    // days since the epoch, see http://howardhinnant.github.io/date_algorithms.html#days_from_civil
+    let (y, m, d) = (year, 12, i64::from(day.into_inner()));
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let doy = (153 * (m - 3) + 2) / 5 + d - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    let days = era * 146_097 + doe - 719_468;
+
+    u64::try_from(days * 86_400 + 5 * 3600).unwrap_or(0)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_duration, unlock_time, Leaderboard, SortBy};
+    use crate::day;
+    use crate::template::runner::strip_ansi;
+
+    const FIXTURE: &str = include_str!("../../data/fixtures/leaderboard.json");
+
+    fn names(leaderboard: &Leaderboard, sort: SortBy, day: Option<crate::Day>) -> Vec<String> {
+        leaderboard
+            .ranking(sort, day)
+            .unwrap()
+            .iter()
+            .map(|member| member.display_name())
+            .collect()
+    }
+
+    #[test]
+    fn parses_fixture() {
+        let leaderboard = Leaderboard::parse(FIXTURE).unwrap();
+        assert_eq!(leaderboard.owner_id, 1001);
+        assert_eq!(leaderboard.members.len(), 3);
+
+        let alice = &leaderboard.members["1001"];
+        assert_eq!(alice.star(day!(1), 2), Some(1_701_408_600));
+        assert_eq!(alice.delta(day!(1)), Some(1488));
+        assert_eq!(leaderboard.members["1002"].delta(day!(2)), None);
+
+        assert!(Leaderboard::parse("{}").is_err());
+    }
+
+    #[test]
+    fn sorts_members() {
+        let leaderboard = Leaderboard::parse(FIXTURE).unwrap();
+
+        assert_eq!(
+            names(&leaderboard, SortBy::Score, None),
+            ["Alice", "Bob", "(anonymous user #1003)"]
+        );
+        assert_eq!(
+            names(&leaderboard, SortBy::Name, None),
+            ["(anonymous user #1003)", "Alice", "Bob"]
+        );
+        assert_eq!(
+            names(&leaderboard, SortBy::Last, None),
+            ["Bob", "Alice", "(anonymous user #1003)"]
+        );
+        assert_eq!(
+            names(&leaderboard, SortBy::Part1, Some(day!(1))),
+            ["Bob", "Alice", "(anonymous user #1003)"]
+        );
+        assert_eq!(
+            names(&leaderboard, SortBy::Delta, Some(day!(1))),
+            ["Alice", "Bob", "(anonymous user #1003)"]
+        );
+        assert!(leaderboard.ranking(SortBy::Delta, None).is_err());
+    }
+
+    #[test]
+    fn renders_overview() {
+        let leaderboard = Leaderboard::parse(FIXTURE).unwrap();
+        let output = strip_ansi(&leaderboard.render_overview(SortBy::Score).unwrap());
+        let lines = output.lines().collect::<Vec<_>>();
+
+        assert_eq!(lines[0], "Private leaderboard 1001 · 2023");
+        assert!(lines[4].starts_with("  1)  Alice"));
+        assert!(lines[4].ends_with("   21   4  ★★·······················"));
+        assert!(lines[5].ends_with("   16   3  ★☆·······················"));
+        assert!(output.ends_with("3 members, 7 stars in total, 2.3 per member\n"));
+    }
+
+    #[test]
+    fn renders_day() {
+        let leaderboard = Leaderboard::parse(FIXTURE).unwrap();
+        let output = strip_ansi(&leaderboard.render_day(day!(1), SortBy::Part2).unwrap());
+
+        assert!(output.contains("Day 01: 2 solved part one, 2 solved part two."));
+        assert!(output.contains("  1)  Alice    00:05:12    00:30:00    00:24:48\n"));
+        assert!(output.contains("  2)  Bob      00:03:00    01:10:00    01:07:00\n"));
+        assert!(!output.contains("anonymous"));
+        assert!(output.ends_with("Fastest part two: Alice (00:24:48).\n"));
+    }
+
+    #[test]
+    fn computes_times() {
+        assert_eq!(unlock_time(2023, day!(1)), 1_701_406_800);
+        assert_eq!(format_duration(3 * 60 + 5), "00:03:05");
+        assert_eq!(format_duration(90_061), "1d 01:01:01");
+    }
+}
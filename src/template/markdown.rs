@@ -0,0 +1,251 @@
+/// Renders the puzzle descriptions saved by `download` for the terminal. Supports the subset of
+/// markdown that aoc-cli produces: headings, emphasis, code spans and blocks, links and lists.
+use crate::template::{runner, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+const CODE_INDENT: &str = "    ";
+
+/// Renders `markdown` with ANSI styles, wrapping paragraphs at `width` columns. Code blocks are
+/// indented and never wrapped.
+pub fn render(markdown: &str, width: usize) -> String {
+    let mut output: Vec<String> = vec![];
+    let mut paragraph: Vec<&str> = vec![];
+    // prefix of the first line of the paragraph, e.g. a list bullet.
+    let mut prefix = "";
+    let mut lines = markdown.lines().peekable();
+
+    let flush = |output: &mut Vec<String>, paragraph: &mut Vec<&str>, prefix: &str| {
+        if !paragraph.is_empty() {
+            let text = render_inline(&paragraph.join(" "));
+            output.extend(wrap(&text, width, prefix));
+            paragraph.clear();
+        }
+    };
+
+    while let Some(line) = lines.next() {
+        let trimmed = line.trim();
+
+        if trimmed.starts_with("```") {
+            flush(&mut output, &mut paragraph, prefix);
+            let mut block = lines
+                .by_ref()
+                .take_while(|line| !line.trim_start().starts_with("```"))
+                .collect::<Vec<_>>();
+            while block.last().is_some_and(|line| line.trim().is_empty()) {
+                block.pop();
+            }
+            output.extend(block.iter().map(|line| format!("{CODE_INDENT}{line}")));
+            output.push(String::new());
+            continue;
+        }
+
+        let is_setext_heading = paragraph.is_empty()
+            && !trimmed.is_empty()
+            && lines.peek().is_some_and(|next| is_underline(next));
+
+        if is_setext_heading || trimmed.starts_with('#') {
+            flush(&mut output, &mut paragraph, prefix);
+            if is_setext_heading {
+                lines.next();
+            }
+            let heading = render_inline(trimmed.trim_start_matches('#').trim());
+            output.push(format!("{ANSI_BOLD}{heading}{ANSI_RESET}"));
+            output.push(String::new());
+            continue;
+        }
+
+        if trimmed.is_empty() {
+            flush(&mut output, &mut paragraph, prefix);
+            if output.last().is_some_and(|line| !line.is_empty()) {
+                output.push(String::new());
+            }
+            continue;
+        }
+
+        if let Some(item) = trimmed
+            .strip_prefix("* ")
+            .or_else(|| trimmed.strip_prefix("- "))
+        {
+            flush(&mut output, &mut paragraph, prefix);
+            prefix = "  • ";
+            paragraph.push(item);
+            continue;
+        }
+
+        if paragraph.is_empty() {
+            prefix = "";
+        }
+        paragraph.push(trimmed);
+    }
+
+    flush(&mut output, &mut paragraph, prefix);
+
+    while output.last().is_some_and(String::is_empty) {
+        output.pop();
+    }
+
+    output.join("\n") + "\n"
+}
+
+fn is_underline(line: &str) -> bool {
+    let line = line.trim();
+    line.len() >= 3 && (line.chars().all(|c| c == '-') || line.chars().all(|c| c == '='))
+}
+
+/// Replaces inline markup with ANSI styles: emphasis is bold, code is italic, links keep their
+/// text. Backslash escapes are resolved.
+fn render_inline(text: &str) -> String {
+    let mut rendered = String::with_capacity(text.len());
+    let mut bold = false;
+    let mut code = false;
+    let mut chars = text.chars().peekable();
+
+    let restyle = |rendered: &mut String, bold: bool, code: bool| {
+        rendered.push_str(ANSI_RESET);
+        if bold {
+            rendered.push_str(ANSI_BOLD);
+        }
+        if code {
+            rendered.push_str(ANSI_ITALIC);
+        }
+    };
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => rendered.extend(chars.next()),
+            '*' => {
+                // `**strong**` is rendered the same as `*emphasis*`.
+                chars.next_if_eq(&'*');
+                bold = !bold;
+                restyle(&mut rendered, bold, code);
+            }
+            '`' => {
+                code = !code;
+                restyle(&mut rendered, bold, code);
+            }
+            '[' => {
+                let rest = chars.clone().collect::<String>();
+                match rest.split_once("](") {
+                    Some((label, after)) if !label.contains(']') && after.contains(')') => {
+                        rendered.push_str(&render_inline(label));
+                        restyle(&mut rendered, bold, code);
+                        let url = &after[..after.find(')').unwrap()];
+                        let skip = label.chars().count() + url.chars().count() + 3;
+                        chars.by_ref().take(skip).for_each(drop);
+                    }
+                    _ => rendered.push(c),
+                }
+            }
+            c => rendered.push(c),
+        }
+    }
+
+    if bold || code {
+        rendered.push_str(ANSI_RESET);
+    }
+
+    rendered
+}
+
+/// Wraps styled text at `width` visible columns. The first line starts with `prefix`, following
+/// lines are indented by its width.
+fn wrap(text: &str, width: usize, prefix: &str) -> Vec<String> {
+    let indent = " ".repeat(prefix.chars().count());
+    let mut lines = vec![];
+    let mut line = prefix.to_string();
+    let mut line_width = indent.len();
+
+    for word in text.split_whitespace() {
+        let word_width = runner::strip_ansi(word).chars().count();
+
+        if line_width > indent.len() && line_width + 1 + word_width > width {
+            lines.push(std::mem::replace(&mut line, indent.clone()));
+            line_width = indent.len();
+        }
+
+        if line_width > indent.len() {
+            line.push(' ');
+            line_width += 1;
+        }
+
+        line.push_str(word);
+        line_width += word_width;
+    }
+
+    lines.push(line);
+    lines
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{render, render_inline, wrap};
+    use crate::template::runner::strip_ansi;
+    use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+    const PUZZLE: &str = r"\--- Day 1: Trebuchet?! ---
+----------
+
+Something is wrong with global snow production, and you've been selected to take a look.
+
+For example:
+
+```
+1abc2
+treb7uchet
+
+```
+
+Adding these together produces `*142*`. See [the calendar](/2023).
+
+* One item.
+* Another item.
+";
+
+    #[test]
+    fn renders_headings_and_blocks() {
+        assert_eq!(
+            strip_ansi(&render(PUZZLE, 40)),
+            "--- Day 1: Trebuchet?! ---
+
+Something is wrong with global snow
+production, and you've been selected to
+take a look.
+
+For example:
+
+    1abc2
+    treb7uchet
+
+Adding these together produces 142. See
+the calendar.
+
+  • One item.
+  • Another item.
+"
+        );
+        assert!(render(PUZZLE, 40).starts_with(&format!("{ANSI_BOLD}--- Day 1")));
+    }
+
+    #[test]
+    fn renders_inline_styles() {
+        assert_eq!(
+            render_inline("a *b* `c` \\*d"),
+            format!(
+                "a {ANSI_RESET}{ANSI_BOLD}b{ANSI_RESET} {ANSI_RESET}{ANSI_ITALIC}c{ANSI_RESET} *d"
+            )
+        );
+        assert_eq!(strip_ansi(&render_inline("`*142*`")), "142");
+        assert_eq!(strip_ansi(&render_inline("[x] and [y](z)")), "[x] and y");
+    }
+
+    #[test]
+    fn wraps_with_hanging_indent() {
+        assert_eq!(
+            wrap("aaa bbb ccc", 9, "- "),
+            vec!["- aaa bbb".to_string(), "  ccc".to_string()]
+        );
+        assert_eq!(
+            wrap("averylongword", 4, ""),
+            vec!["averylongword".to_string()]
+        );
+    }
+}
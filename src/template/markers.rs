@@ -0,0 +1,229 @@
+/// Parser for the sections of the readme that are generated by the template. A section starts
+/// and ends with the same marker, e.g. `<!--- benchmarking table --->`. Several sections of one
+/// kind are told apart by a name: `<!--- benchmarking table:release --->`.
+///
+/// Only comments with three dashes whose kind ends with `table` are markers. Markers in code
+/// blocks and code spans are ignored, so they can be documented in the readme itself.
+use std::fmt::Display;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Marker {
+    pub kind: String,
+    pub name: Option<String>,
+    /// Line of the marker, starting at 1.
+    pub line: usize,
+    start: usize,
+    end: usize,
+}
+
+impl Marker {
+    fn is_same_section(&self, other: &Self) -> bool {
+        self.kind == other.kind && self.name == other.name
+    }
+}
+
+impl Display for Marker {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", format_marker(&self.kind, self.name.as_deref()))
+    }
+}
+
+/// A section including its start and end markers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Section {
+    pub start: Marker,
+    pub end: Marker,
+}
+
+impl Section {
+    /// Byte offset of the start marker.
+    pub fn pos_start(&self) -> usize {
+        self.start.start
+    }
+
+    /// Byte offset after the end marker.
+    pub fn pos_end(&self) -> usize {
+        self.end.end
+    }
+}
+
+pub fn format_marker(kind: &str, name: Option<&str>) -> String {
+    match name {
+        Some(name) => format!("<!--- {kind}:{name} --->"),
+        None => format!("<!--- {kind} --->"),
+    }
+}
+
+/// Finds all markers in `readme`, in order.
+fn find_markers(readme: &str) -> Vec<Marker> {
+    let mut markers = vec![];
+    let mut offset = 0;
+    let mut in_code_block = false;
+
+    for (i, line) in readme.split_inclusive('\n').enumerate() {
+        let line_offset = offset;
+        offset += line.len();
+
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_code_block = !in_code_block;
+            continue;
+        }
+
+        if in_code_block {
+            continue;
+        }
+
+        let mut rest = 0;
+        while let Some(start) = line[rest..].find("<!---").map(|x| x + rest) {
+            let Some(end) = line[start..].find("--->").map(|x| x + start + 4) else {
+                break;
+            };
+            rest = end;
+
+            // an odd number of backticks before the marker opens a code span.
+            if line[..start].matches('`').count() % 2 == 1 {
+                continue;
+            }
+
+            let content = line[start + 5..end - 4].trim();
+            let (kind, name) = match content.split_once(':') {
+                Some((kind, name)) => (kind.trim(), Some(name.trim().to_string())),
+                None => (content, None),
+            };
+
+            if kind.ends_with("table") {
+                markers.push(Marker {
+                    kind: kind.to_string(),
+                    name,
+                    line: i + 1,
+                    start: line_offset + start,
+                    end: line_offset + end,
+                });
+            }
+        }
+    }
+
+    markers
+}
+
+/// Pairs up all markers of `readme` into sections. Sections must not overlap and every section
+/// must only appear once.
+pub fn parse(readme: &str) -> Result<Vec<Section>, String> {
+    let mut sections: Vec<Section> = vec![];
+    let mut open: Option<Marker> = None;
+
+    for marker in find_markers(readme) {
+        match open.take() {
+            None => open = Some(marker),
+            Some(start) if start.is_same_section(&marker) => {
+                if let Some(other) = sections.iter().find(|x| x.start.is_same_section(&start)) {
+                    return Err(format!(
+                        "line {}: section \"{start}\" already appeared at line {}.",
+                        start.line, other.start.line
+                    ));
+                }
+
+                sections.push(Section { start, end: marker });
+            }
+            Some(start) => {
+                return Err(format!(
+                    "line {}: marker \"{marker}\" is inside the section \"{start}\" that starts at line {}.",
+                    marker.line, start.line
+                ));
+            }
+        }
+    }
+
+    match open {
+        Some(marker) => Err(format!(
+            "line {}: marker \"{marker}\" is never closed.",
+            marker.line
+        )),
+        None => Ok(sections),
+    }
+}
+
+/// Locates the section `kind` with the optional `name`.
+pub fn locate(readme: &str, kind: &str, name: Option<&str>) -> Result<Section, String> {
+    parse(readme)?
+        .into_iter()
+        .find(|x| x.start.kind == kind && x.start.name.as_deref() == name)
+        .ok_or_else(|| {
+            format!(
+                "could not find the section \"{}\".",
+                format_marker(kind, name)
+            )
+        })
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{locate, parse};
+
+    const TABLE: &str = "<!--- benchmarking table --->";
+    const RELEASE: &str = "<!--- benchmarking table:release --->";
+    const STARS: &str = "<!--- advent_readme_stars table --->";
+
+    #[test]
+    fn locates_named_sections() {
+        let readme = format!("# readme\n{TABLE}\na\n{TABLE}\n{RELEASE}\nb\n{RELEASE}\n");
+
+        let section = locate(&readme, "benchmarking table", None).unwrap();
+        assert_eq!(section.start.line, 2);
+        assert_eq!(section.end.line, 4);
+        assert_eq!(
+            &readme[section.pos_start()..section.pos_end()],
+            format!("{TABLE}\na\n{TABLE}")
+        );
+
+        let section = locate(&readme, "benchmarking table", Some("release")).unwrap();
+        assert_eq!(
+            &readme[section.pos_start()..section.pos_end()],
+            format!("{RELEASE}\nb\n{RELEASE}")
+        );
+
+        assert_eq!(
+            locate(&readme, "benchmarking table", Some("debug")).unwrap_err(),
+            "could not find the section \"<!--- benchmarking table:debug --->\"."
+        );
+    }
+
+    #[test]
+    fn reports_unclosed_markers() {
+        let readme = format!("{TABLE}\n{TABLE}\n\n{TABLE}\n");
+        assert_eq!(
+            parse(&readme).unwrap_err(),
+            "line 4: marker \"<!--- benchmarking table --->\" is never closed."
+        );
+    }
+
+    #[test]
+    fn reports_overlapping_sections() {
+        let readme = format!("{STARS}\n{TABLE}\n{STARS}\n{TABLE}\n");
+        assert_eq!(
+            parse(&readme).unwrap_err(),
+            "line 2: marker \"<!--- benchmarking table --->\" is inside the section \"<!--- advent_readme_stars table --->\" that starts at line 1."
+        );
+    }
+
+    #[test]
+    fn reports_duplicate_sections() {
+        let readme = format!("{TABLE}{TABLE}\n\n{TABLE}{TABLE}\n");
+        assert_eq!(
+            parse(&readme).unwrap_err(),
+            "line 3: section \"<!--- benchmarking table --->\" already appeared at line 1."
+        );
+    }
+
+    #[test]
+    fn ignores_markers_in_code() {
+        let readme = format!(
+            "Use `{RELEASE}` to name a table.\n\n```md\n{TABLE}\n```\n{TABLE}\n{TABLE}\n<!--- a comment --->\n"
+        );
+
+        let sections = parse(&readme).unwrap();
+        assert_eq!(sections.len(), 1);
+        assert_eq!(sections[0].start.line, 6);
+    }
+}
@@ -1,22 +1,45 @@
 use crate::Day;
 use std::{env, fs};
 
+pub mod alloc_stats;
 pub mod aoc_cli;
+pub mod cache;
+pub mod cases;
+pub mod checksums;
 pub mod commands;
+pub mod config;
+pub mod encryption;
+pub mod environment;
+pub mod input;
+pub mod leaderboard;
+pub mod markdown;
+pub mod markers;
+pub mod params;
+pub mod profiler;
+pub mod puzzle;
 pub mod readme_benchmarks;
+pub mod readme_stars;
+pub mod render;
+pub mod reports;
 pub mod runner;
+pub mod submissions;
+mod terminal;
+pub mod trace;
+pub mod visualize;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
 /// Helper function that reads a text file to a string.
+/// Encrypted inputs are decrypted transparently and checked against the checksum manifest, see
+/// [`encryption`] and [`checksums`].
 #[must_use]
 pub fn read_file(folder: &str, day: Day) -> String {
     let cwd = env::current_dir().unwrap();
     let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+    let f = input::read_input_file(&filepath);
+    f.unwrap_or_else(|e| panic!("could not open input file: {e}"))
 }
 
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
@@ -32,17 +55,83 @@ pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
 }
 
 /// Creates the constant `DAY` and sets up the input and runner for each part.
+///
+/// Optionally declares parameters with their defaults, which the solution reads via the generated
+/// `params()` function. See [`params`] for how to override them.
+///
+/// ```ignore
+/// advent_of_code::solution!(21, steps: usize = 64);
+///
+/// pub fn part_one(input: &str) -> Option<u32> {
+///     let steps = params().steps;
+///     // ...
+/// }
+/// ```
 #[macro_export]
 macro_rules! solution {
-    ($day:expr) => {
+    ($day:expr $(, $param:ident : $type:ty = $default:expr)* $(,)?) => {
         /// The current day.
         const DAY: advent_of_code::Day = advent_of_code::day!($day);
 
+        /// Parameters of the solution, see `advent_of_code::template::params`.
+        #[allow(dead_code)]
+        #[derive(Debug, Clone)]
+        struct Params {
+            $($param: $type,)*
+        }
+
+        /// Returns the parameters of the solution, taking overrides into account.
+        #[allow(dead_code)]
+        fn params() -> Params {
+            Params {
+                $($param: advent_of_code::template::params::get(stringify!($param), $default),)*
+            }
+        }
+
         fn main() {
             use advent_of_code::template::runner::*;
-            let input = advent_of_code::template::read_file("inputs", DAY);
+            advent_of_code::template::params::init_from_args(&[$(stringify!($param)),*]);
+            advent_of_code::template::trace::init_from_args();
+            advent_of_code::template::visualize::init_from_args();
+            advent_of_code::template::render::init_from_args();
+            advent_of_code::template::environment::init_from_args();
+            let input = advent_of_code::template::input::read_from_args(DAY);
             run_part(part_one, &input, DAY, 1);
             run_part(part_two, &input, DAY, 2);
+            advent_of_code::template::visualize::finish(DAY);
+            advent_of_code::template::render::finish();
+        }
+    };
+}
+
+/// Defines a `benchmark` function that runs the given functions of a solution under criterion,
+/// with the puzzle input as read by [`read_file`]. Used by the bench targets in `benches/`, which
+/// include the solution module:
+///
+/// ```ignore
+/// #[allow(dead_code, unused_imports)]
+/// mod solution {
+///     include!("../src/bin/04.rs");
+///
+///     advent_of_code::benchmark!(parse, part_one, part_two);
+/// }
+///
+/// criterion::criterion_group!(benches, solution::benchmark);
+/// criterion::criterion_main!(benches);
+/// ```
+#[macro_export]
+macro_rules! benchmark {
+    ($($func:ident),+ $(,)?) => {
+        /// Benchmarks the solution with the puzzle input.
+        pub fn benchmark(c: &mut ::criterion::Criterion) {
+            let input = advent_of_code::template::read_file("inputs", DAY);
+            let mut group = c.benchmark_group(DAY.to_string());
+            $(
+                group.bench_function(stringify!($func), |b| {
+                    b.iter(|| $func(::std::hint::black_box(&input)))
+                });
+            )+
+            group.finish();
         }
     };
 }
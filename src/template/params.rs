@@ -0,0 +1,166 @@
+/// Per-day parameters for constants that differ between the examples and the real input, such as
+/// the number of steps to simulate. Parameters are declared with their defaults in the
+/// `solution!` macro, e.g. `solution!(21, steps: usize = 64)`, and read via the generated
+/// `params()` function. The defaults can be overridden:
+///  1. on the command line, via `cargo solve 21 --param steps=6`.
+///  2. in tests, via [`with`].
+///  3. in the sidecar file of a named example case, see [`crate::template::cases`].
+use std::cell::RefCell;
+use std::str::FromStr;
+use std::sync::Mutex;
+use std::{env, process};
+
+/// Overrides passed on the command line.
+static ARGS: Mutex<Vec<(String, String)>> = Mutex::new(Vec::new());
+
+thread_local! {
+    /// Overrides set by [`with`]. Thread-local, so tests running in parallel don't interfere.
+    static SCOPED: RefCell<Vec<(String, String)>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Splits a `name=value` override.
+pub fn parse_override(s: &str) -> Result<(String, String), String> {
+    match s.split_once('=') {
+        Some((name, value)) if !name.trim().is_empty() => {
+            Ok((name.trim().to_string(), value.trim().to_string()))
+        }
+        _ => Err(format!(
+            "expecting a parameter as `name=value`, found \"{s}\""
+        )),
+    }
+}
+
+/// Reads overrides from the `--param name=value` arguments of the solution binary. Called by the
+/// `solution!` macro with the names of the declared parameters; unknown names are an error.
+pub fn init_from_args(declared: &[&str]) {
+    let args: Vec<String> = env::args().collect();
+
+    let overrides = args
+        .iter()
+        .enumerate()
+        .filter(|(_, arg)| *arg == "--param")
+        .map(|(i, _)| args.get(i + 1).map_or("", String::as_str))
+        .map(parse_override)
+        .collect::<Result<Vec<_>, _>>();
+
+    let overrides = match overrides {
+        Ok(overrides) => overrides,
+        Err(e) => {
+            eprintln!("Invalid --param: {e}.");
+            process::exit(1);
+        }
+    };
+
+    for (name, _) in &overrides {
+        if !declared.contains(&name.as_str()) {
+            eprintln!(
+                "Unknown parameter \"{name}\". This solution declares: {}.",
+                if declared.is_empty() {
+                    "none".to_string()
+                } else {
+                    declared.join(", ")
+                }
+            );
+            process::exit(1);
+        }
+    }
+
+    if !overrides.is_empty() {
+        let overrides_str = overrides
+            .iter()
+            .map(|(name, value)| format!("{name}={value}"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        eprintln!("Using parameters: {overrides_str}");
+    }
+
+    *ARGS.lock().unwrap() = overrides;
+}
+
+/// Runs `f` with the given parameters overridden on the current thread.
+///
+/// ```ignore
+/// let result = advent_of_code::template::params::with(&[("steps", "6")], || part_one(input));
+/// ```
+pub fn with<K, V, T>(overrides: &[(K, V)], f: impl FnOnce() -> T) -> T
+where
+    K: AsRef<str>,
+    V: AsRef<str>,
+{
+    let previous = SCOPED.with(|scoped| {
+        let mut scoped = scoped.borrow_mut();
+        let previous = scoped.clone();
+        scoped.extend(
+            overrides
+                .iter()
+                .map(|(k, v)| (k.as_ref().to_string(), v.as_ref().to_string())),
+        );
+        previous
+    });
+
+    let result = f();
+    SCOPED.with(|scoped| *scoped.borrow_mut() = previous);
+    result
+}
+
+/// Returns the value of the parameter `name`: the innermost override set by [`with`], otherwise
+/// the value passed on the command line, otherwise `default`. Panics if an override can't be
+/// parsed, since the solution can't run with it.
+pub fn get<T: FromStr>(name: &str, default: T) -> T {
+    let find = |overrides: &[(String, String)]| {
+        overrides
+            .iter()
+            .rev()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.clone())
+    };
+
+    let value = SCOPED
+        .with(|scoped| find(&scoped.borrow()))
+        .or_else(|| find(&ARGS.lock().unwrap()));
+
+    match value {
+        Some(value) => value
+            .parse()
+            .unwrap_or_else(|_| panic!("invalid value \"{value}\" for parameter \"{name}\"")),
+        None => default,
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{get, parse_override, with};
+
+    #[test]
+    fn uses_defaults() {
+        assert_eq!(get("steps", 64_usize), 64);
+    }
+
+    #[test]
+    fn overrides_in_scope() {
+        let steps = with(&[("steps", "6")], || {
+            let inner = with(&[("steps", "7")], || get("steps", 64_usize));
+            assert_eq!(inner, 7);
+            get("steps", 64_usize)
+        });
+
+        assert_eq!(steps, 6);
+        assert_eq!(get("steps", 64_usize), 64);
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid value \"x\" for parameter \"steps\"")]
+    fn panics_on_invalid_values() {
+        with(&[("steps", "x")], || get("steps", 64_usize));
+    }
+
+    #[test]
+    fn parses_overrides() {
+        assert_eq!(
+            parse_override("steps = 6"),
+            Ok(("steps".to_string(), "6".to_string()))
+        );
+        assert!(parse_override("steps").is_err());
+        assert!(parse_override("=6").is_err());
+    }
+}
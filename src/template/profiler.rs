@@ -0,0 +1,145 @@
+/// Flamegraphs for `cargo solve <day> --profile <part>`. The part runs in a loop under `perf` if it
+/// is installed, or under the built-in sampler of the `profile` feature otherwise. Both produce
+/// folded stacks, one line `frame;frame;frame <samples>` per distinct stack, which are rendered
+/// to `target/profiles/<day>-<part>.svg`. Rendering needs the `profile` feature as well.
+use std::env;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use crate::Day;
+
+/// How long a part is run in a loop while it is profiled.
+pub const DURATION: Duration = Duration::from_secs(5);
+
+/// Samples per second.
+pub const FREQUENCY: u32 = 999;
+
+/// The cargo target directory, `CARGO_TARGET_DIR` if set.
+pub fn get_target_dir() -> PathBuf {
+    PathBuf::from(env::var("CARGO_TARGET_DIR").unwrap_or_else(|_| "target".into()))
+}
+
+/// Directory of the flamegraphs and the intermediate files of the profilers.
+pub fn get_profile_dir() -> PathBuf {
+    get_target_dir().join("profiles")
+}
+
+pub fn get_svg_path(day: Day, part: u8) -> PathBuf {
+    get_profile_dir().join(format!("{day}-{part}.svg"))
+}
+
+pub fn get_folded_path(day: Day, part: u8) -> PathBuf {
+    get_profile_dir().join(format!("{day}-{part}.folded"))
+}
+
+/// Collapses the output of `perf script` into folded stacks.
+#[cfg(feature = "profile")]
+pub fn collapse_perf(script: &str) -> std::io::Result<Vec<String>> {
+    use inferno::collapse::perf::{Folder, Options as CollapseOptions};
+    use inferno::collapse::Collapse;
+
+    let mut folded = vec![];
+    Folder::from(CollapseOptions::default()).collapse(script.as_bytes(), &mut folded)?;
+    Ok(String::from_utf8_lossy(&folded)
+        .lines()
+        .map(ToString::to_string)
+        .collect())
+}
+
+/// Renders folded stacks to a flamegraph SVG at `path`.
+#[cfg(feature = "profile")]
+pub fn write_flamegraph(folded: &[String], title: &str, path: &PathBuf) -> Result<(), String> {
+    use inferno::flamegraph::{self, Options as FlamegraphOptions};
+    use std::fs::File;
+    use std::io::BufWriter;
+
+    if folded.is_empty() {
+        return Err("no samples were recorded".into());
+    }
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+
+    let file = File::create(path).map_err(|e| e.to_string())?;
+
+    let mut options = FlamegraphOptions::default();
+    options.title = title.to_string();
+    options.count_name = "samples".into();
+
+    flamegraph::from_lines(
+        &mut options,
+        folded.iter().map(String::as_str),
+        BufWriter::new(file),
+    )
+    .map_err(|e| e.to_string())
+}
+
+/// Runs `func` repeatedly for [`DURATION`] under the built-in sampler and returns the folded
+/// stacks.
+#[cfg(feature = "profile")]
+pub fn sample<T>(mut func: impl FnMut() -> T) -> Result<Vec<String>, String> {
+    use std::fmt::Write;
+    use std::time::Instant;
+
+    let guard = pprof::ProfilerGuardBuilder::default()
+        .frequency(i32::try_from(FREQUENCY).unwrap_or(i32::MAX))
+        .blocklist(&["libc", "libgcc", "pthread", "vdso"])
+        .build()
+        .map_err(|e| e.to_string())?;
+
+    let start = Instant::now();
+    while start.elapsed() < DURATION {
+        std::hint::black_box(func());
+    }
+
+    let report = guard.report().build().map_err(|e| e.to_string())?;
+
+    // same format as the `flamegraph` feature of pprof: outermost frame first.
+    let folded = report
+        .data
+        .iter()
+        .map(|(frames, samples)| {
+            let mut line = frames.thread_name_or_id();
+
+            for frame in frames.frames.iter().rev() {
+                for symbol in frame.iter().rev() {
+                    let _ = write!(line, ";{symbol}");
+                }
+            }
+
+            let _ = write!(line, " {samples}");
+            line
+        })
+        .collect();
+
+    Ok(folded)
+}
+
+#[cfg(all(feature = "test_lib", feature = "profile"))]
+mod tests {
+    use super::collapse_perf;
+
+    /// Samples are weighted by the period of the event, so the count is in cycles.
+    #[test]
+    fn collapses_perf_script() {
+        let script = [
+            "04 12345 1000.000001:    1001001 cycles:u: ",
+            "\t    55d0c0a01234 advent_of_code::solve+0x14 (/target/profiling/04)",
+            "\t    55d0c0a05678 04::part_two+0x20 (/target/profiling/04)",
+            "\t    55d0c0a09abc main+0x10 (/target/profiling/04)",
+            "",
+            "04 12345 1000.001002:    1001001 cycles:u: ",
+            "\t    55d0c0a01234 advent_of_code::solve+0x18 (/target/profiling/04)",
+            "\t    55d0c0a05678 04::part_two+0x20 (/target/profiling/04)",
+            "\t    55d0c0a09abc main+0x10 (/target/profiling/04)",
+            "",
+        ]
+        .join("\n");
+
+        assert_eq!(
+            collapse_perf(&script).unwrap(),
+            vec!["04;main;04::part_two;advent_of_code::solve 2002002".to_string()]
+        );
+    }
+}
@@ -0,0 +1,167 @@
+/// Extracts examples and their expected answers from puzzle descriptions saved by `download`.
+/// aoc-cli converts the puzzle HTML to markdown: `<pre><code>` example blocks become fenced code
+/// blocks and answers, which the puzzles highlight as `<code><em>142</em></code>`, become `` `*142*` ``.
+use std::fs;
+
+use regex::Regex;
+
+use crate::template::aoc_cli;
+use crate::Day;
+
+/// Examples and highlighted answers found in the description of one part of a puzzle.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct PuzzlePart {
+    /// Contents of all code blocks of the part, in order of appearance.
+    pub examples: Vec<String>,
+    /// All emphasised code spans of the part, in order of appearance. The expected answer for the
+    /// example is usually the last one.
+    pub answers: Vec<String>,
+}
+
+/// Reads and parses the puzzle description saved by `download`.
+pub fn read(day: Day) -> Option<Vec<PuzzlePart>> {
+    fs::read_to_string(aoc_cli::get_puzzle_path(day))
+        .ok()
+        .map(|puzzle| parse(&puzzle))
+}
+
+/// Splits a puzzle description into the markdown of each part, starting with the part's heading.
+/// Anything before the first heading is dropped.
+pub fn split_parts(puzzle: &str) -> Vec<String> {
+    let mut parts: Vec<String> = vec![];
+
+    for line in puzzle.lines() {
+        if is_part_heading(line) {
+            parts.push(String::new());
+        }
+
+        if let Some(part) = parts.last_mut() {
+            part.push_str(line);
+            part.push('\n');
+        }
+    }
+
+    parts
+}
+
+fn is_part_heading(line: &str) -> bool {
+    line.contains("--- Day ") || line.contains("--- Part Two ---")
+}
+
+/// Splits a puzzle description into its parts. Part two is only present once part one has been
+/// solved and the description was downloaded again.
+pub fn parse(puzzle: &str) -> Vec<PuzzlePart> {
+    let mut parts = vec![];
+    let mut current: Option<PuzzlePart> = None;
+    let mut lines = puzzle.lines();
+
+    let answer_pattern = Regex::new(r"`\*([^*`]+)\*`|\*`([^*`]+)`\*").unwrap();
+
+    while let Some(line) = lines.next() {
+        if is_part_heading(line) {
+            parts.extend(current.replace(PuzzlePart::default()));
+            continue;
+        }
+
+        let Some(part) = current.as_mut() else {
+            continue;
+        };
+
+        if line.starts_with("```") {
+            let block = lines
+                .by_ref()
+                .take_while(|line| !line.starts_with("```"))
+                .collect::<Vec<_>>()
+                .join("\n");
+
+            let example = block.trim_end_matches('\n');
+            if !example.is_empty() {
+                part.examples.push(format!("{example}\n"));
+            }
+            continue;
+        }
+
+        part.answers
+            .extend(answer_pattern.captures_iter(line).map(|captures| {
+                captures
+                    .get(1)
+                    .or_else(|| captures.get(2))
+                    .unwrap()
+                    .as_str()
+                    .to_string()
+            }));
+    }
+
+    parts.extend(current);
+    parts
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse, split_parts, PuzzlePart};
+
+    const PUZZLE: &str = r"\--- Day 1: Trebuchet?! ---
+----------
+
+For example:
+
+```
+1abc2
+treb7uchet
+
+```
+
+In this example, the calibration values are `12` and `77`. Adding these together produces `*89*`.
+
+Consider your *entire* calibration document.
+
+Your puzzle answer was `54159`.
+
+\--- Part Two ---
+----------
+
+Some of the digits are spelled out, e.g. *`one`*:
+
+```
+two1nine
+eightwothree
+
+```
+
+Adding these together produces `*112*`.
+";
+
+    #[test]
+    fn parses_parts() {
+        assert_eq!(
+            parse(PUZZLE),
+            vec![
+                PuzzlePart {
+                    examples: vec!["1abc2\ntreb7uchet\n".into()],
+                    answers: vec!["89".into()],
+                },
+                PuzzlePart {
+                    examples: vec!["two1nine\neightwothree\n".into()],
+                    answers: vec!["one".into(), "112".into()],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_part_one_only() {
+        let puzzle = PUZZLE.split("\\--- Part Two").next().unwrap();
+        assert_eq!(parse(puzzle).len(), 1);
+        assert!(parse("").is_empty());
+    }
+
+    #[test]
+    fn splits_parts() {
+        let parts = split_parts(PUZZLE);
+
+        assert_eq!(parts.len(), 2);
+        assert!(parts[0].starts_with("\\--- Day 1: Trebuchet?! ---\n"));
+        assert!(parts[0].ends_with("Your puzzle answer was `54159`.\n\n"));
+        assert!(parts[1].starts_with("\\--- Part Two ---\n"));
+    }
+}
@@ -2,9 +2,17 @@
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
 use std::{fs, io};
 
+use crate::template::alloc_stats::{self, Stats};
+use crate::template::markers;
+use crate::template::reports::Report;
 use crate::Day;
 
-static MARKER: &str = "<!--- benchmarking table --->";
+/// Kind of the markers around the table, see [`crate::template::markers`].
+static MARKER_KIND: &str = "benchmarking table";
+
+/// Timings of the last benchmark, so that they can be shown in tables that are updated without
+/// running the solutions, see [`crate::template::readme_stars`].
+pub const TIMINGS_FILE: &str = "data/timings.txt";
 
 #[derive(Debug)]
 pub enum Error {
@@ -18,17 +26,24 @@ impl From<std::io::Error> for Error {
     }
 }
 
-#[derive(Clone)]
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parser(e) => write!(f, "{e}"),
+            Error::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
 pub struct Timings {
     pub day: Day,
     pub part_1: Option<String>,
     pub part_2: Option<String>,
     pub total_nanos: f64,
-}
-
-pub struct TablePosition {
-    pos_start: usize,
-    pos_end: usize,
+    /// Heap usage, if the solution was built with the `alloc_stats` feature.
+    pub part_1_memory: Option<Stats>,
+    pub part_2_memory: Option<Stats>,
 }
 
 #[must_use]
@@ -36,76 +51,170 @@ pub fn get_path_for_bin(day: Day) -> String {
     format!("./src/bin/{day}.rs")
 }
 
-fn locate_table(readme: &str) -> Result<TablePosition, Error> {
-    let matches: Vec<_> = readme.match_indices(MARKER).collect();
-
-    if matches.len() > 2 {
-        return Err(Error::Parser(
-            "{}: too many occurences of marker in README.".into(),
-        ));
-    }
-
-    let pos_start = matches
-        .first()
-        .map(|m| m.0)
-        .ok_or_else(|| Error::Parser("Could not find table start position.".into()))?;
+fn construct_table(marker: &str, heading: &str, report: &Report) -> String {
+    let Report {
+        timings,
+        total_millis,
+        environment,
+    } = *report;
+    let mut lines: Vec<String> = vec![marker.into(), heading.into(), String::new()];
 
-    let pos_end = matches
-        .last()
-        .map(|m| m.0 + m.1.len())
-        .ok_or_else(|| Error::Parser("Could not find table end position.".into()))?;
-
-    Ok(TablePosition { pos_start, pos_end })
-}
+    // the peak heap is only shown if the solutions were built with the `alloc_stats` feature.
+    let with_memory = timings
+        .iter()
+        .any(|x| x.part_1_memory.is_some() || x.part_2_memory.is_some());
 
-fn construct_table(prefix: &str, timings: Vec<Timings>, total_millis: f64) -> String {
-    let header = format!("{prefix} Benchmarks");
+    if with_memory {
+        lines.push("| Day | Part 1 | Part 2 | Part 1 peak heap | Part 2 peak heap |".into());
+        lines.push("| :---: | :---: | :---:  | :---: | :---: |".into());
+    } else {
+        lines.push("| Day | Part 1 | Part 2 |".into());
+        lines.push("| :---: | :---: | :---:  |".into());
+    }
 
-    let mut lines: Vec<String> = vec![
-        MARKER.into(),
-        header,
-        String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
-    ];
+    let peak = |memory: Option<&Stats>| {
+        memory.map_or_else(|| "-".into(), |x| alloc_stats::format_bytes(x.peak_bytes))
+    };
 
     for timing in timings {
         let path = get_path_for_bin(timing.day);
-        lines.push(format!(
+        let mut line = format!(
             "| [Day {}]({}) | `{}` | `{}` |",
             timing.day.into_inner(),
             path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
-        ));
+            timing.part_1.as_deref().unwrap_or("-"),
+            timing.part_2.as_deref().unwrap_or("-")
+        );
+
+        if with_memory {
+            line.push_str(&format!(
+                " `{}` | `{}` |",
+                peak(timing.part_1_memory.as_ref()),
+                peak(timing.part_2_memory.as_ref())
+            ));
+        }
+
+        lines.push(line);
     }
 
     lines.push(String::new());
     lines.push(format!("**Total: {total_millis:.2}ms**"));
-    lines.push(MARKER.into());
+
+    if let Some(environment) = environment {
+        lines.push(String::new());
+        lines.push(format!("_{environment}_"));
+
+        for warning in &environment.warnings {
+            lines.push(String::new());
+            lines.push(format!("> ⚠️ {warning}"));
+        }
+    }
+
+    lines.push(marker.into());
 
     lines.join("\n")
 }
 
-fn update_content(s: &mut String, timings: Vec<Timings>, total_millis: f64) -> Result<(), Error> {
-    let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
-    s.replace_range(positions.pos_start..positions.pos_end, &table);
-    Ok(())
+/// Replaces the benchmarking table, or the one with the marker `<!--- benchmarking table:name --->`
+/// if a `name` is given.
+pub fn update_content(
+    s: &mut String,
+    name: Option<&str>,
+    heading: &str,
+    timings: &[Timings],
+    total_millis: f64,
+) -> Result<(), Error> {
+    let report = Report {
+        timings,
+        total_millis,
+        environment: None,
+    };
+    update_report(s, name, heading, &report)
 }
 
-pub fn update(timings: Vec<Timings>, total_millis: f64) -> Result<(), Error> {
-    let path = "README.md";
-    let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
-    update_content(&mut readme, timings, total_millis)?;
-    fs::write(path, &readme)?;
+/// Like [`update_content`], but also shows the environment of the report below the table.
+pub(crate) fn update_report(
+    s: &mut String,
+    name: Option<&str>,
+    heading: &str,
+    report: &Report,
+) -> Result<(), Error> {
+    let section = markers::locate(s, MARKER_KIND, name).map_err(Error::Parser)?;
+    let marker = markers::format_marker(MARKER_KIND, name);
+    let table = construct_table(&marker, heading, report);
+    s.replace_range(section.pos_start()..section.pos_end(), &table);
     Ok(())
 }
 
+pub fn save_timings(timings: &[Timings]) -> io::Result<()> {
+    fs::write(TIMINGS_FILE, format_timings(timings))
+}
+
+/// Reads the timings of the last benchmark. Returns no timings if there was no benchmark yet.
+pub fn load_timings() -> io::Result<Vec<Timings>> {
+    match fs::read_to_string(TIMINGS_FILE) {
+        Ok(timings) => Ok(parse_timings(&timings)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(vec![]),
+        Err(e) => Err(e),
+    }
+}
+
+fn format_timings(timings: &[Timings]) -> String {
+    let mut lines = vec!["# day  part 1  part 2  total (ns)".to_string()];
+
+    for timing in timings {
+        lines.push(format!(
+            "{}  {}  {}  {}",
+            timing.day,
+            timing.part_1.as_deref().unwrap_or("-"),
+            timing.part_2.as_deref().unwrap_or("-"),
+            timing.total_nanos
+        ));
+    }
+
+    lines.join("\n") + "\n"
+}
+
+fn parse_timings(timings: &str) -> Vec<Timings> {
+    let part = |s: &str| (s != "-").then(|| s.to_string());
+
+    timings
+        .lines()
+        .filter(|line| !line.starts_with('#'))
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            Some(Timings {
+                day: fields.next()?.parse().ok()?,
+                part_1: part(fields.next()?),
+                part_2: part(fields.next()?),
+                total_nanos: fields.next()?.parse().ok()?,
+                part_1_memory: None,
+                part_2_memory: None,
+            })
+        })
+        .collect()
+}
+
+/// Parses a duration as printed by the runner, e.g. `12.3µs`, into nanoseconds.
+pub fn parse_duration(s: &str) -> Option<f64> {
+    // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
+    let parse = |postfix: &str| s.split(postfix).next()?.trim().parse::<f64>().ok();
+
+    match s {
+        s if s.contains("ns") => parse("ns"),
+        s if s.contains("µs") => parse("µs").map(|x| x * 1000_f64),
+        s if s.contains("ms") => parse("ms").map(|x| x * 1_000_000_f64),
+        _ => parse("s").map(|x| x * 1_000_000_000_f64),
+    }
+}
+
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{update_content, Timings, MARKER};
+    use super::{format_timings, parse_duration, parse_timings, update_content, Timings};
     use crate::day;
+    use crate::template::alloc_stats::Stats;
+
+    const MARKER: &str = "<!--- benchmarking table --->";
 
     fn get_mock_timings() -> Vec<Timings> {
         vec![
@@ -114,18 +223,24 @@ mod tests {
                 part_1: Some("10ms".into()),
                 part_2: Some("20ms".into()),
                 total_nanos: 3e+10,
+                part_1_memory: None,
+                part_2_memory: None,
             },
             Timings {
                 day: day!(2),
                 part_1: Some("30ms".into()),
                 part_2: Some("40ms".into()),
                 total_nanos: 7e+10,
+                part_1_memory: None,
+                part_2_memory: None,
             },
             Timings {
                 day: day!(4),
                 part_1: Some("40ms".into()),
                 part_2: Some("50ms".into()),
                 total_nanos: 9e+10,
+                part_1_memory: None,
+                part_2_memory: None,
             },
         ]
     }
@@ -134,36 +249,82 @@ mod tests {
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, None, "## Benchmarks", &get_mock_timings(), 190.0).unwrap();
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
         let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, None, "## Benchmarks", &get_mock_timings(), 190.0).unwrap();
     }
 
     #[test]
     fn updates_empty_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, None, "## Benchmarks", &get_mock_timings(), 190.0).unwrap();
         assert_eq!(s.contains("## Benchmarks"), true);
     }
 
     #[test]
     fn updates_existing_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, None, "## Benchmarks", &get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, None, "## Benchmarks", &get_mock_timings(), 190.0).unwrap();
         assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
         assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
     }
 
+    #[test]
+    fn updates_named_benchmarks() {
+        let release = "<!--- benchmarking table:release --->";
+        let mut s = format!("{MARKER}\nold\n{MARKER}\n{release}{release}\n");
+        update_content(
+            &mut s,
+            Some("release"),
+            "## Release",
+            &get_mock_timings(),
+            190.0,
+        )
+        .unwrap();
+
+        assert!(s.starts_with(&format!("{MARKER}\nold\n{MARKER}\n{release}\n## Release\n")));
+        assert!(s.ends_with(&format!("**Total: 190.00ms**\n{release}\n")));
+    }
+
+    #[test]
+    fn reports_unpaired_markers() {
+        let mut s = format!("foo\n{MARKER}\n{MARKER}\n{MARKER}\n");
+        let error = update_content(&mut s, None, "## Benchmarks", &get_mock_timings(), 190.0)
+            .unwrap_err()
+            .to_string();
+        assert_eq!(
+            error,
+            "line 4: marker \"<!--- benchmarking table --->\" is never closed."
+        );
+    }
+
+    #[test]
+    fn format_benchmarks_with_memory() {
+        let mut timings = get_mock_timings();
+        timings[0].part_1_memory = Some(Stats {
+            peak_bytes: 1536,
+            total_bytes: 4096,
+            allocations: 3,
+        });
+
+        let mut s = format!("{MARKER}{MARKER}");
+        update_content(&mut s, None, "## Benchmarks", &timings, 190.0).unwrap();
+
+        assert!(s.contains("| Day | Part 1 | Part 2 | Part 1 peak heap | Part 2 peak heap |\n"));
+        assert!(s.contains("| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` | `1.5 KiB` | `-` |\n"));
+        assert!(s.contains("| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` | `-` | `-` |\n"));
+    }
+
     #[test]
     fn format_benchmarks() {
         let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, None, "## Benchmarks", &get_mock_timings(), 190.0).unwrap();
         let expected = [
             "foo",
             "bar",
@@ -183,4 +344,22 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn parses_durations() {
+        assert_eq!(parse_duration("74.13ns"), Some(74.13));
+        assert_eq!(parse_duration("2.5µs"), Some(2500.0));
+        assert_eq!(parse_duration("100ms"), Some(100_000_000.0));
+        assert_eq!(parse_duration("2s"), Some(2_000_000_000.0));
+        assert_eq!(parse_duration("-"), None);
+    }
+
+    #[test]
+    fn round_trips_timings() {
+        let timings = get_mock_timings();
+        let formatted = format_timings(&timings);
+
+        assert!(formatted.contains("\n01  10ms  20ms  30000000000\n"));
+        assert_eq!(parse_timings(&formatted), timings);
+    }
 }
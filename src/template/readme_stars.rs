@@ -0,0 +1,290 @@
+/// Module that updates the readme with the ⭐️ progress, in the same format as the
+/// `advent-readme-stars` action. Stars are read from the local submission log and from the known
+/// answers in `data/inputs/NN.case`, so no request to the website is needed.
+use std::collections::BTreeMap;
+use std::fs;
+
+use crate::template::aoc_cli;
+use crate::template::cases::ExampleCase;
+use crate::template::config::Config;
+use crate::template::input::Source;
+use crate::template::markers;
+use crate::template::readme_benchmarks::{self, Error, Timings};
+use crate::template::submissions::{self, Submission};
+use crate::{all_days, Day};
+
+/// Kind of the markers around the table, see [`crate::template::markers`].
+static MARKER_KIND: &str = "advent_readme_stars table";
+
+/// Solved parts by day.
+pub type Stars = BTreeMap<Day, [bool; 2]>;
+
+/// Collects the stars from correct submissions and from the days with known answers.
+pub fn collect(submissions: &[Submission]) -> Stars {
+    let mut stars = Stars::new();
+
+    for submission in submissions.iter().filter(|x| x.verdict.is_solved()) {
+        if let Some(part) = usize::from(submission.part)
+            .checked_sub(1)
+            .filter(|x| *x < 2)
+        {
+            stars.entry(submission.day).or_default()[part] = true;
+        }
+    }
+
+    for day in all_days() {
+        for (part, known) in known_answers(day).iter().enumerate() {
+            if known.is_some() {
+                stars.entry(day).or_default()[part] = true;
+            }
+        }
+    }
+
+    stars
+}
+
+/// Known answers of the default input, from the sidecar file `data/inputs/NN.case`.
+fn known_answers(day: Day) -> [Option<String>; 2] {
+    let Some(path) = Source::Default.path(day) else {
+        return [None, None];
+    };
+
+    match fs::read_to_string(path.with_extension("case")) {
+        Ok(sidecar) => ExampleCase::parse(&day.to_string(), &sidecar)
+            .map(|case| [case.part_one, case.part_two])
+            .unwrap_or_default(),
+        Err(_) => [None, None],
+    }
+}
+
+/// Builds the progress table. With `timings`, the timings of the last benchmark are shown next
+/// to the stars.
+fn construct_table(year: Option<u16>, stars: &Stars, timings: Option<&[Timings]>) -> String {
+    let header = match year {
+        Some(year) => format!("## {year} Results"),
+        None => "## Results".into(),
+    };
+
+    let marker = markers::format_marker(MARKER_KIND, None);
+
+    let mut lines: Vec<String> = vec![
+        marker.clone(),
+        header,
+        String::new(),
+        "| Day | Part 1 | Part 2 |".into(),
+        "| :---: | :---: | :---: |".into(),
+    ];
+
+    let timing = |day: Day| timings.and_then(|x| x.iter().find(|timing| timing.day == day));
+
+    for day in all_days() {
+        let solved = stars.get(&day).copied().unwrap_or_default();
+        let timing = timing(day);
+
+        if !solved.contains(&true) && timing.is_none() {
+            continue;
+        }
+
+        let link = match year {
+            Some(year) => format!(
+                "[Day {}](https://adventofcode.com/{year}/day/{})",
+                day.into_inner(),
+                day.into_inner()
+            ),
+            None => format!("Day {}", day.into_inner()),
+        };
+
+        let cell = |part: usize| {
+            let star = if solved[part] { "⭐" } else { " " };
+            let time = timing.and_then(|x| {
+                if part == 0 {
+                    x.part_1.as_ref()
+                } else {
+                    x.part_2.as_ref()
+                }
+            });
+
+            match (timings, time) {
+                (Some(_), Some(time)) => format!("{star} `{time}`"),
+                _ => star.to_string(),
+            }
+        };
+
+        lines.push(format!("| {link} | {} | {} |", cell(0), cell(1)));
+    }
+
+    if let Some(timings) = timings.filter(|x| !x.is_empty()) {
+        let total_millis = timings.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64;
+        lines.push(String::new());
+        lines.push(format!("**Total: {total_millis:.2}ms**"));
+    }
+
+    lines.push(marker);
+    lines.join("\n")
+}
+
+/// Reads the stars from an existing progress table, e.g. one written by the Github action.
+fn parse_table(table: &str) -> Stars {
+    let mut stars = Stars::new();
+
+    for line in table.lines() {
+        let cells = line.split('|').map(str::trim).collect::<Vec<_>>();
+        let [_, link, part_1, part_2, _] = cells[..] else {
+            continue;
+        };
+
+        let day = link
+            .strip_prefix("[Day ")
+            .or_else(|| link.strip_prefix("Day "))
+            .and_then(|x| x.split(']').next())
+            .and_then(|x| x.parse::<u8>().ok())
+            .and_then(Day::new);
+
+        if let Some(day) = day {
+            stars.insert(day, [part_1.contains('⭐'), part_2.contains('⭐')]);
+        }
+    }
+
+    stars
+}
+
+/// Stars can not be lost, so the stars already in the table are kept.
+fn update_content(
+    s: &mut String,
+    year: Option<u16>,
+    stars: &Stars,
+    timings: Option<&[Timings]>,
+) -> Result<(), Error> {
+    let section = markers::locate(s, MARKER_KIND, None).map_err(Error::Parser)?;
+    let range = section.pos_start()..section.pos_end();
+
+    let mut stars = stars.clone();
+    for (day, solved) in parse_table(&s[range.clone()]) {
+        let entry = stars.entry(day).or_default();
+        entry[0] |= solved[0];
+        entry[1] |= solved[1];
+    }
+
+    let table = construct_table(year, &stars, timings);
+    s.replace_range(range, &table);
+    Ok(())
+}
+
+/// Updates the progress table from the submission log and the known answers. If configured, the
+/// timings of the last benchmark are shown next to the stars.
+pub fn refresh() -> Result<(), String> {
+    let config = Config::load()?;
+    let stars = collect(&submissions::load()?);
+
+    let timings = if config.readme.combine_stars_and_timings {
+        Some(readme_benchmarks::load_timings().map_err(|e| e.to_string())?)
+    } else {
+        None
+    };
+
+    update(aoc_cli::get_year(), &stars, timings.as_deref()).map_err(|e| e.to_string())
+}
+
+pub fn update(year: Option<u16>, stars: &Stars, timings: Option<&[Timings]>) -> Result<(), Error> {
+    let path = "README.md";
+    let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
+    update_content(&mut readme, year, stars, timings)?;
+    fs::write(path, &readme)?;
+    Ok(())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{collect, update_content, Stars};
+    use crate::day;
+    use crate::template::readme_benchmarks::Timings;
+    use crate::template::submissions::{Submission, Verdict};
+
+    const MARKER: &str = "<!--- advent_readme_stars table --->";
+
+    fn get_mock_stars() -> Stars {
+        [(day!(1), [true, true]), (day!(21), [true, false])].into()
+    }
+
+    #[test]
+    fn collects_stars_from_submissions() {
+        let submission = |day, part, verdict| Submission {
+            timestamp: 0,
+            day,
+            part,
+            answer: "1".into(),
+            verdict,
+        };
+
+        let stars = collect(&[
+            submission(day!(24), 1, Verdict::TooLow),
+            submission(day!(24), 1, Verdict::Correct),
+            submission(day!(24), 2, Verdict::Wrong),
+            submission(day!(25), 2, Verdict::AlreadySolved),
+        ]);
+
+        assert_eq!(stars.get(&day!(24)), Some(&[true, false]));
+        assert_eq!(stars.get(&day!(25)), Some(&[false, true]));
+    }
+
+    #[test]
+    #[should_panic]
+    fn errors_if_marker_not_present() {
+        let mut s = "# readme".to_string();
+        update_content(&mut s, Some(2023), &get_mock_stars(), None).unwrap();
+    }
+
+    #[test]
+    fn format_stars() {
+        let mut s = format!("foo\n{MARKER}\nold\n{MARKER}\nbaz");
+        update_content(&mut s, Some(2023), &get_mock_stars(), None).unwrap();
+        let expected = [
+            "foo",
+            "<!--- advent_readme_stars table --->",
+            "## 2023 Results",
+            "",
+            "| Day | Part 1 | Part 2 |",
+            "| :---: | :---: | :---: |",
+            "| [Day 1](https://adventofcode.com/2023/day/1) | ⭐ | ⭐ |",
+            "| [Day 21](https://adventofcode.com/2023/day/21) | ⭐ |   |",
+            "<!--- advent_readme_stars table --->",
+            "baz",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
+
+    #[test]
+    fn keeps_existing_stars() {
+        let mut s = format!("{MARKER}\n| [Day 2](https://adventofcode.com/2023/day/2) | ⭐ | ⭐ |\n| [Day 21](https://adventofcode.com/2023/day/21) | ⭐ |   |\n{MARKER}");
+        update_content(
+            &mut s,
+            Some(2023),
+            &[(day!(21), [false, true])].into(),
+            None,
+        )
+        .unwrap();
+
+        assert!(s.contains("| [Day 2](https://adventofcode.com/2023/day/2) | ⭐ | ⭐ |\n"));
+        assert!(s.contains("| [Day 21](https://adventofcode.com/2023/day/21) | ⭐ | ⭐ |\n"));
+    }
+
+    #[test]
+    fn format_stars_with_timings() {
+        let timings = [Timings {
+            day: day!(1),
+            part_1: Some("10ms".into()),
+            part_2: None,
+            total_nanos: 1e+7,
+            part_1_memory: None,
+            part_2_memory: None,
+        }];
+
+        let mut s = format!("{MARKER}{MARKER}");
+        update_content(&mut s, Some(2023), &get_mock_stars(), Some(&timings)).unwrap();
+
+        assert!(s.contains("| [Day 1](https://adventofcode.com/2023/day/1) | ⭐ `10ms` | ⭐ |\n"));
+        assert!(s.contains("| [Day 21](https://adventofcode.com/2023/day/21) | ⭐ |   |\n"));
+        assert!(s.contains("\n**Total: 10.00ms**\n"));
+    }
+}
@@ -0,0 +1,117 @@
+/// Saves images of a solution's state to disk.
+/// Solutions pass a [`GridImage`] to [`output`], which is a no-op unless the solution binary is
+/// invoked with `--render <path>`, e.g. through the `render` command. The last image output by
+/// each part is saved next to `path` with the part as a suffix, e.g. `10-1.svg` and `10-2.svg`.
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::{env, fs};
+
+use crate::template::runner;
+use crate::{GridImage, ImageFormat, Rgb};
+
+static TARGET: Mutex<Option<(PathBuf, ImageFormat)>> = Mutex::new(None);
+static IMAGES: Mutex<Vec<(u8, Vec<u8>)>> = Mutex::new(Vec::new());
+
+/// Enables rendering if the solution binary was invoked with `--render <path>`.
+/// Called by the `solution!` macro before running the parts.
+pub fn init_from_args() {
+    let args: Vec<String> = env::args().collect();
+
+    let Some(path) = args
+        .iter()
+        .position(|x| x == "--render")
+        .and_then(|i| args.get(i + 1))
+    else {
+        return;
+    };
+
+    match ImageFormat::from_path(path) {
+        Some(format) => *TARGET.lock().unwrap() = Some((PathBuf::from(path), format)),
+        None => {
+            eprintln!("Cannot render to \"{path}\": expecting a .ppm or .svg file.");
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Returns whether images passed to [`output`] are saved.
+pub fn enabled() -> bool {
+    TARGET.lock().unwrap().is_some() && runner::current_part().is_some()
+}
+
+/// Keeps the image to be saved once the solution has finished. The image is only encoded if
+/// rendering is enabled, so it is cheap to call this unconditionally.
+pub fn output<T, F>(image: &GridImage<'_, T, F>)
+where
+    T: Clone,
+    F: Fn(&(usize, usize), &T) -> Rgb,
+{
+    let Some(part) = runner::current_part() else {
+        return;
+    };
+
+    let Some(format) = TARGET.lock().unwrap().as_ref().map(|(_, format)| *format) else {
+        return;
+    };
+
+    let mut images = IMAGES.lock().unwrap();
+    images.retain(|(p, _)| *p != part);
+    images.push((part, image.encode(format)));
+}
+
+fn path_for_part(path: &Path, part: u8) -> PathBuf {
+    let stem = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let extension = path
+        .extension()
+        .map(|extension| extension.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    path.with_file_name(format!("{stem}-{part}.{extension}"))
+}
+
+/// Writes all images output by the parts. Called by the `solution!` macro after running the parts.
+pub fn finish() {
+    let Some((path, _)) = TARGET.lock().unwrap().clone() else {
+        return;
+    };
+
+    let images = std::mem::take(&mut *IMAGES.lock().unwrap());
+
+    if images.is_empty() {
+        eprintln!("No images were rendered. Use `advent_of_code::template::render::output` in your solution.");
+        return;
+    }
+
+    if let Some(parent) = path.parent() {
+        let _ = fs::create_dir_all(parent);
+    }
+
+    for (part, image) in images {
+        let path = path_for_part(&path, part);
+        match fs::write(&path, image) {
+            Ok(()) => eprintln!("Saved image for part {part} to \"{}\".", path.display()),
+            Err(e) => eprintln!("Failed to save image for part {part}: {e}"),
+        }
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::path_for_part;
+    use std::path::{Path, PathBuf};
+
+    #[test]
+    fn appends_part_to_path() {
+        assert_eq!(
+            path_for_part(Path::new("target/renders/10.svg"), 2),
+            PathBuf::from("target/renders/10-2.svg")
+        );
+        assert_eq!(
+            path_for_part(Path::new("out.ppm"), 1),
+            PathBuf::from("out-1.ppm")
+        );
+    }
+}
@@ -0,0 +1,590 @@
+/// Benchmark reports written by `cargo all --release --time`. Each report target renders the
+/// timings in one format to one file; the targets are configured in `.aoc/config.toml`:
+///
+/// ```toml
+/// [[reports]]
+/// format = "readme"
+///
+/// [[reports]]
+/// format = "html"
+/// path = "data/reports/benchmarks.html"
+/// ```
+///
+/// Every run is also appended to the history in [`HISTORY_FILE`], together with its environment.
+use std::fmt::Display;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::template::alloc_stats::Stats;
+use crate::template::environment::Environment;
+use crate::template::readme_benchmarks::{self, Error, Timings};
+
+/// One JSON summary per line, see [`append_history`].
+pub const HISTORY_FILE: &str = "data/benchmarks.jsonl";
+
+/// Timings of one benchmark run of all days.
+pub struct Report<'a> {
+    pub timings: &'a [Timings],
+    pub total_millis: f64,
+    /// Machine and build the timings were measured on.
+    pub environment: Option<&'a Environment>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Format {
+    /// Benchmarking table between the markers of a markdown file.
+    Readme,
+    /// Standalone page with a bar chart per day.
+    Html,
+    Csv,
+    Json,
+}
+
+impl Format {
+    fn default_path(self) -> &'static str {
+        match self {
+            Self::Readme => "README.md",
+            Self::Html => "data/reports/benchmarks.html",
+            Self::Csv => "data/reports/benchmarks.csv",
+            Self::Json => "data/reports/benchmarks.json",
+        }
+    }
+
+    fn renderer(self) -> Box<dyn Renderer> {
+        match self {
+            Self::Readme => Box::new(Readme),
+            Self::Html => Box::new(Html),
+            Self::Csv => Box::new(Csv),
+            Self::Json => Box::new(Json),
+        }
+    }
+}
+
+impl Display for Format {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let format = match self {
+            Self::Readme => "readme",
+            Self::Html => "html",
+            Self::Csv => "csv",
+            Self::Json => "json",
+        };
+        write!(f, "{format}")
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Target {
+    pub format: Format,
+    /// Defaults to a path depending on the format, see [`Target::path`].
+    pub path: Option<String>,
+    /// Heading of the readme table.
+    pub heading: Option<String>,
+    /// Name of the readme table, to update the table with the marker
+    /// `<!--- benchmarking table:name --->` instead of the unnamed one.
+    pub section: Option<String>,
+}
+
+impl Target {
+    pub fn new(format: Format) -> Self {
+        Self {
+            format,
+            path: None,
+            heading: None,
+            section: None,
+        }
+    }
+
+    pub fn path(&self) -> &str {
+        self.path
+            .as_deref()
+            .unwrap_or_else(|| self.format.default_path())
+    }
+
+    /// Renders the report and writes it to the target path. Readme targets update the table in
+    /// the existing file, all other targets replace the file.
+    pub fn write(&self, report: &Report) -> Result<(), Error> {
+        let path = Path::new(self.path());
+
+        let existing = match self.format {
+            Format::Readme => Some(String::from_utf8_lossy(&fs::read(path)?).to_string()),
+            _ => None,
+        };
+
+        let content = self.format.renderer().render(self, report, existing)?;
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        fs::write(path, content)?;
+        Ok(())
+    }
+}
+
+/// Renders a report into the content of a target file.
+pub trait Renderer {
+    /// `existing` is the current content of the target file, for renderers that update a file.
+    fn render(
+        &self,
+        target: &Target,
+        report: &Report,
+        existing: Option<String>,
+    ) -> Result<String, Error>;
+}
+
+pub struct Readme;
+
+impl Renderer for Readme {
+    fn render(
+        &self,
+        target: &Target,
+        report: &Report,
+        existing: Option<String>,
+    ) -> Result<String, Error> {
+        let mut readme = existing.unwrap_or_default();
+        let heading = target.heading.as_deref().unwrap_or("## Benchmarks");
+        readme_benchmarks::update_report(&mut readme, target.section.as_deref(), heading, report)?;
+        Ok(readme)
+    }
+}
+
+pub struct Html;
+
+impl Renderer for Html {
+    fn render(&self, _: &Target, report: &Report, _: Option<String>) -> Result<String, Error> {
+        // all bars share one scale, so that days can be compared.
+        let max_nanos = report
+            .timings
+            .iter()
+            .flat_map(|x| [part_nanos(x.part_1.as_ref()), part_nanos(x.part_2.as_ref())])
+            .fold(0_f64, f64::max);
+
+        let bar = |part: u8, time: Option<&String>, memory: Option<&Stats>| {
+            let width = match part_nanos(time) {
+                nanos if max_nanos > 0_f64 => nanos / max_nanos * 100_f64,
+                _ => 0_f64,
+            };
+
+            let memory = memory.map_or_else(String::new, |x| {
+                format!("<small>{}</small>", escape(&x.to_string()))
+            });
+
+            format!(
+                "      <div class=\"part\"><span>Part {part}</span><div class=\"bar part-{part}\" style=\"width: {width:.2}%\"></div><code>{}</code>{memory}</div>",
+                escape(time.map_or("-", String::as_str))
+            )
+        };
+
+        let mut lines = vec![
+            "<!DOCTYPE html>".to_string(),
+            "<html lang=\"en\">".into(),
+            "<head>".into(),
+            "  <meta charset=\"utf-8\">".into(),
+            "  <title>Benchmarks</title>".into(),
+            "  <style>".into(),
+            "    body { font-family: sans-serif; max-width: 50rem; margin: 2rem auto; }".into(),
+            "    .day { margin-bottom: 1rem; }".into(),
+            "    .part { display: flex; align-items: center; gap: 0.5rem; }".into(),
+            "    .part span { width: 4rem; }".into(),
+            "    .part small { color: #777; }".into(),
+            "    .bar { height: 1rem; min-width: 1px; }".into(),
+            "    .part-1 { background: #9b59b6; }".into(),
+            "    .part-2 { background: #f1c40f; }".into(),
+            "    .environment { color: #777; }".into(),
+            "  </style>".into(),
+            "</head>".into(),
+            "<body>".into(),
+            "  <h1>Benchmarks</h1>".into(),
+        ];
+
+        for timing in report.timings {
+            lines.push("  <div class=\"day\">".into());
+            lines.push(format!("    <h2>Day {}</h2>", timing.day.into_inner()));
+            lines.push(bar(
+                1,
+                timing.part_1.as_ref(),
+                timing.part_1_memory.as_ref(),
+            ));
+            lines.push(bar(
+                2,
+                timing.part_2.as_ref(),
+                timing.part_2_memory.as_ref(),
+            ));
+            lines.push("  </div>".into());
+        }
+
+        lines.push(format!(
+            "  <p><strong>Total: {:.2}ms</strong></p>",
+            report.total_millis
+        ));
+
+        if let Some(environment) = report.environment {
+            lines.push(format!(
+                "  <p class=\"environment\">{}</p>",
+                escape(&environment.to_string())
+            ));
+
+            for warning in &environment.warnings {
+                lines.push(format!("  <p class=\"warning\">⚠️ {}</p>", escape(warning)));
+            }
+        }
+
+        lines.push("</body>".into());
+        lines.push("</html>".into());
+
+        Ok(lines.join("\n") + "\n")
+    }
+}
+
+pub struct Csv;
+
+impl Renderer for Csv {
+    fn render(&self, _: &Target, report: &Report, _: Option<String>) -> Result<String, Error> {
+        let mut lines = vec![[
+            "day,part_1,part_2,part_1_nanos,part_2_nanos,total_nanos",
+            "part_1_peak_bytes,part_1_total_bytes,part_1_allocations",
+            "part_2_peak_bytes,part_2_total_bytes,part_2_allocations",
+            "cpu,cores,rustc,target_cpu,opt_level,git_revision,pinned_core",
+        ]
+        .join(",")];
+
+        // the environment is repeated on every row, so that the reports of several runs can be
+        // concatenated.
+        let environment = report.environment.map_or_else(
+            || ",,,,,,".into(),
+            |x| {
+                [
+                    csv_field(x.cpu.as_deref().unwrap_or_default()),
+                    x.cores.to_string(),
+                    csv_field(x.rustc.as_deref().unwrap_or_default()),
+                    csv_field(x.target_cpu.as_deref().unwrap_or_default()),
+                    csv_field(&x.opt_level),
+                    csv_field(x.git_revision.as_deref().unwrap_or_default()),
+                    x.pinned_core.map(|x| x.to_string()).unwrap_or_default(),
+                ]
+                .join(",")
+            },
+        );
+
+        for timing in report.timings {
+            let nanos = |time: Option<&String>| {
+                time.and_then(|x| readme_benchmarks::parse_duration(x))
+                    .map(|x| x.to_string())
+                    .unwrap_or_default()
+            };
+
+            // memory columns stay empty without the `alloc_stats` feature.
+            let memory = |memory: Option<&Stats>| {
+                memory.map_or_else(
+                    || ",,".into(),
+                    |x| format!("{},{},{}", x.peak_bytes, x.total_bytes, x.allocations),
+                )
+            };
+
+            lines.push(format!(
+                "{},{},{},{},{},{},{},{},{environment}",
+                timing.day.into_inner(),
+                timing.part_1.as_deref().unwrap_or_default(),
+                timing.part_2.as_deref().unwrap_or_default(),
+                nanos(timing.part_1.as_ref()),
+                nanos(timing.part_2.as_ref()),
+                timing.total_nanos,
+                memory(timing.part_1_memory.as_ref()),
+                memory(timing.part_2_memory.as_ref())
+            ));
+        }
+
+        Ok(lines.join("\n") + "\n")
+    }
+}
+
+pub struct Json;
+
+#[derive(Serialize)]
+struct JsonSummary<'a> {
+    total_millis: f64,
+    environment: Option<&'a Environment>,
+    days: Vec<JsonDay>,
+}
+
+#[derive(Serialize)]
+struct JsonDay {
+    day: u8,
+    part_1: Option<String>,
+    part_2: Option<String>,
+    part_1_nanos: Option<f64>,
+    part_2_nanos: Option<f64>,
+    total_nanos: f64,
+    part_1_memory: Option<Stats>,
+    part_2_memory: Option<Stats>,
+}
+
+impl<'a> JsonSummary<'a> {
+    fn new(report: &Report<'a>) -> Self {
+        Self {
+            total_millis: report.total_millis,
+            environment: report.environment,
+            days: report
+                .timings
+                .iter()
+                .map(|timing| JsonDay {
+                    day: timing.day.into_inner(),
+                    part_1: timing.part_1.clone(),
+                    part_2: timing.part_2.clone(),
+                    part_1_nanos: timing
+                        .part_1
+                        .as_deref()
+                        .and_then(readme_benchmarks::parse_duration),
+                    part_2_nanos: timing
+                        .part_2
+                        .as_deref()
+                        .and_then(readme_benchmarks::parse_duration),
+                    total_nanos: timing.total_nanos,
+                    part_1_memory: timing.part_1_memory,
+                    part_2_memory: timing.part_2_memory,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl Renderer for Json {
+    fn render(&self, _: &Target, report: &Report, _: Option<String>) -> Result<String, Error> {
+        serde_json::to_string_pretty(&JsonSummary::new(report))
+            .map(|json| json + "\n")
+            .map_err(|e| Error::Parser(e.to_string()))
+    }
+}
+
+#[derive(Serialize)]
+struct HistoryEntry<'a> {
+    /// Seconds since the unix epoch.
+    timestamp: u64,
+    #[serde(flatten)]
+    summary: JsonSummary<'a>,
+}
+
+/// Appends the report to the history, so that timings can be compared across runs and machines.
+pub fn append_history(report: &Report) -> Result<(), Error> {
+    let entry = HistoryEntry {
+        timestamp: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |x| x.as_secs()),
+        summary: JsonSummary::new(report),
+    };
+
+    let line = serde_json::to_string(&entry).map_err(|e| Error::Parser(e.to_string()))?;
+
+    if let Some(parent) = Path::new(HISTORY_FILE).parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(HISTORY_FILE)?;
+    writeln!(file, "{line}")?;
+    Ok(())
+}
+
+fn part_nanos(time: Option<&String>) -> f64 {
+    time.and_then(|x| readme_benchmarks::parse_duration(x))
+        .unwrap_or_default()
+}
+
+/// Quotes a CSV field if it contains a separator or a quote.
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{csv_field, Csv, Format, Html, Json, Readme, Renderer, Report, Target};
+    use crate::day;
+    use crate::template::alloc_stats::Stats;
+    use crate::template::environment::Environment;
+    use crate::template::readme_benchmarks::Timings;
+
+    fn get_mock_timings() -> Vec<Timings> {
+        vec![
+            Timings {
+                day: day!(1),
+                part_1: Some("10.00ms".into()),
+                part_2: Some("20.00ms".into()),
+                total_nanos: 3e+7,
+                part_1_memory: None,
+                part_2_memory: None,
+            },
+            Timings {
+                day: day!(2),
+                part_1: Some("5.00µs".into()),
+                part_2: None,
+                total_nanos: 5e+3,
+                part_1_memory: Some(Stats {
+                    peak_bytes: 512,
+                    total_bytes: 1024,
+                    allocations: 2,
+                }),
+                part_2_memory: None,
+            },
+        ]
+    }
+
+    fn get_mock_environment() -> Environment {
+        Environment {
+            cpu: Some("Intel(R) Core(TM) i7-8550U CPU @ 1.80GHz".into()),
+            cores: 8,
+            rustc: Some("rustc 1.75.0 (82e1608df 2023-12-21)".into()),
+            target_cpu: None,
+            opt_level: "3".into(),
+            git_revision: Some("1a2b3c4".into()),
+            pinned_core: None,
+            warnings: vec![
+                "High system load (6.50 on 8 cores), timings may be slower than usual.".into(),
+            ],
+        }
+    }
+
+    fn render_with(
+        renderer: &dyn Renderer,
+        target: &Target,
+        existing: Option<String>,
+        environment: Option<&Environment>,
+    ) -> String {
+        let timings = get_mock_timings();
+        let report = Report {
+            timings: &timings,
+            total_millis: 30.25,
+            environment,
+        };
+        renderer.render(target, &report, existing).unwrap()
+    }
+
+    fn render(renderer: &dyn Renderer, target: &Target, existing: Option<String>) -> String {
+        render_with(renderer, target, existing, None)
+    }
+
+    #[test]
+    fn renders_readme_with_heading() {
+        let mut target = Target::new(Format::Readme);
+        target.heading = Some("### Release".into());
+        target.section = Some("release".into());
+
+        let marker = "<!--- benchmarking table:release --->";
+        let readme = render(&Readme, &target, Some(format!("foo\n{marker}{marker}\n")));
+
+        assert!(readme.starts_with(&format!("foo\n{marker}\n### Release\n")));
+        assert!(readme.contains("| [Day 2](./src/bin/02.rs) | `5.00µs` | `-` | `512 B` | `-` |\n"));
+    }
+
+    #[test]
+    fn renders_html() {
+        let html = render(&Html, &Target::new(Format::Html), None);
+
+        assert!(html.contains("<h2>Day 1</h2>"));
+        assert!(html
+            .contains("class=\"bar part-2\" style=\"width: 100.00%\"></div><code>20.00ms</code>"));
+        assert!(
+            html.contains("class=\"bar part-1\" style=\"width: 0.03%\"></div><code>5.00µs</code>")
+        );
+        assert!(html.contains("class=\"bar part-2\" style=\"width: 0.00%\"></div><code>-</code>"));
+        assert!(html.contains("<strong>Total: 30.25ms</strong>"));
+    }
+
+    #[test]
+    fn renders_csv() {
+        let csv = render(&Csv, &Target::new(Format::Csv), None);
+
+        assert_eq!(
+            csv,
+            [
+                "day,part_1,part_2,part_1_nanos,part_2_nanos,total_nanos,part_1_peak_bytes,part_1_total_bytes,part_1_allocations,part_2_peak_bytes,part_2_total_bytes,part_2_allocations,cpu,cores,rustc,target_cpu,opt_level,git_revision,pinned_core",
+                "1,10.00ms,20.00ms,10000000,20000000,30000000,,,,,,,,,,,,,",
+                "2,5.00µs,,5000,,5000,512,1024,2,,,,,,,,,,",
+                "",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn renders_json() {
+        let json = render(&Json, &Target::new(Format::Json), None);
+        let summary: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(summary["total_millis"], 30.25);
+        assert_eq!(summary["days"][0]["day"], 1);
+        assert_eq!(summary["days"][0]["part_2_nanos"], 2e+7);
+        assert_eq!(summary["days"][1]["part_2"], serde_json::Value::Null);
+        assert_eq!(summary["days"][1]["part_1_memory"]["peak_bytes"], 512);
+        assert_eq!(summary["days"][0]["part_1_memory"], serde_json::Value::Null);
+    }
+
+    #[test]
+    fn renders_environment() {
+        let environment = get_mock_environment();
+        let description = "Intel(R) Core(TM) i7-8550U CPU @ 1.80GHz (8 cores) · rustc 1.75.0 (82e1608df 2023-12-21) · opt-level=3 · git 1a2b3c4";
+        let warning = "⚠️ High system load (6.50 on 8 cores), timings may be slower than usual.";
+
+        let marker = "<!--- benchmarking table --->";
+        let readme = render_with(
+            &Readme,
+            &Target::new(Format::Readme),
+            Some(format!("{marker}{marker}")),
+            Some(&environment),
+        );
+        assert!(readme.ends_with(&format!(
+            "**Total: 30.25ms**\n\n_{description}_\n\n> {warning}\n{marker}"
+        )));
+
+        let html = render_with(&Html, &Target::new(Format::Html), None, Some(&environment));
+        assert!(html.contains(&format!("<p class=\"environment\">{description}</p>")));
+        assert!(html.contains(&format!("<p class=\"warning\">{warning}</p>")));
+
+        let csv = render_with(&Csv, &Target::new(Format::Csv), None, Some(&environment));
+        assert!(csv.contains(
+            ",,,Intel(R) Core(TM) i7-8550U CPU @ 1.80GHz,8,rustc 1.75.0 (82e1608df 2023-12-21),,3,1a2b3c4,\n"
+        ));
+
+        let json = render_with(&Json, &Target::new(Format::Json), None, Some(&environment));
+        let summary: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(summary["environment"]["cores"], 8);
+        assert_eq!(
+            summary["environment"]["target_cpu"],
+            serde_json::Value::Null
+        );
+        assert_eq!(summary["environment"]["git_revision"], "1a2b3c4");
+    }
+
+    #[test]
+    fn quotes_csv_fields() {
+        assert_eq!(csv_field("AMD Ryzen 7"), "AMD Ryzen 7");
+        assert_eq!(csv_field("a, \"b\""), "\"a, \"\"b\"\"\"");
+    }
+
+    #[test]
+    fn uses_default_paths() {
+        assert_eq!(Target::new(Format::Readme).path(), "README.md");
+        assert_eq!(
+            Target::new(Format::Html).path(),
+            "data/reports/benchmarks.html"
+        );
+    }
+}
@@ -1,21 +1,55 @@
 /// Encapsulates code that interacts with solution functions.
-use crate::template::{aoc_cli, ANSI_ITALIC, ANSI_RESET};
-use crate::Day;
+use crate::template::submissions::{self, Submission, Verdict};
+use crate::template::{
+    alloc_stats, aoc_cli, cache, profiler, readme_stars, ANSI_ITALIC, ANSI_RESET,
+};
+use crate::{answer, Day};
 use std::fmt::Display;
 use std::io::{stdout, Write};
 use std::process::Output;
+use std::sync::atomic::{AtomicU8, Ordering};
 use std::time::{Duration, Instant};
 use std::{cmp, env, process};
 
 use super::ANSI_BOLD;
 
+/// The part whose first execution is in progress, `0` otherwise.
+static CURRENT_PART: AtomicU8 = AtomicU8::new(0);
+
+/// Returns the part that is currently being solved, or `None` outside of a part's first execution.
+/// Benchmark iterations don't count as first execution, so side effects such as recording frames
+/// for a visualisation can use this to run only once per part.
+pub fn current_part() -> Option<u8> {
+    match CURRENT_PART.load(Ordering::Relaxed) {
+        0 => None,
+        part => Some(part),
+    }
+}
+
 pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+    if let Some(profiled) = profiled_part() {
+        if profiled == part {
+            profile_part(func, input, day, part);
+        }
+        return;
+    }
+
     let part_str = format!("Part {part}");
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    CURRENT_PART.store(part, Ordering::Relaxed);
+
+    let (result, duration, samples, stats) = run_timed(func, input, |result| {
+        CURRENT_PART.store(0, Ordering::Relaxed);
+        print_result(result, &part_str, "");
+    });
+
+    let mut duration_str = format_duration(&duration, samples);
+    if let Some(stats) = stats {
+        duration_str.push_str(&format!(" [{stats}]"));
+    }
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    print_result(&result, &part_str, &duration_str);
+    print_narrowing_warnings(&part_str);
 
     if let Some(result) = result {
         submit_result(result, day, part);
@@ -25,14 +59,20 @@ pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, d
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
 ///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+///
+/// With the `alloc_stats` feature, the heap usage of the first execution is returned, too.
 fn run_timed<I: Clone, T>(
     func: impl Fn(I) -> T,
     input: I,
     hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+) -> (T, Duration, u128, Option<alloc_stats::Stats>) {
+    let cloned = input.clone();
+
+    alloc_stats::reset();
     let timer = Instant::now();
-    let result = func(input.clone());
+    let result = func(cloned);
     let base_time = timer.elapsed();
+    let stats = alloc_stats::is_enabled().then(alloc_stats::stats);
 
     hook(&result);
 
@@ -42,7 +82,55 @@ fn run_timed<I: Clone, T>(
         (base_time, 1)
     };
 
-    (result, run.0, run.1)
+    (result, run.0, run.1, stats)
+}
+
+/// The part passed to `--profile`, see [`crate::template::profiler`].
+fn profiled_part() -> Option<u8> {
+    let args: Vec<String> = env::args().collect();
+    let index = args.iter().position(|x| x == "--profile")?;
+
+    match args.get(index + 1).and_then(|x| x.parse().ok()) {
+        Some(part) => Some(part),
+        None => {
+            eprintln!("Unexpected command-line input. Format: cargo solve 1 --profile 1");
+            process::exit(1);
+        }
+    }
+}
+
+/// Runs a part in a loop while it is profiled. Without the `profile` feature, the samples are
+/// taken by an external profiler such as `perf`.
+fn profile_part<I: Clone, T>(func: impl Fn(I) -> T, input: I, day: Day, part: u8) {
+    println!(
+        "Profiling part {part} for {}s...",
+        profiler::DURATION.as_secs()
+    );
+
+    #[cfg(feature = "profile")]
+    {
+        let path = profiler::get_folded_path(day, part);
+
+        let result = profiler::sample(|| func(input.clone())).and_then(|folded| {
+            std::fs::create_dir_all(profiler::get_profile_dir())
+                .and_then(|()| std::fs::write(&path, folded.join("\n") + "\n"))
+                .map_err(|e| e.to_string())
+        });
+
+        if let Err(e) = result {
+            eprintln!("Failed to profile part {part} of day {day}: {e}");
+            process::exit(1);
+        }
+    }
+
+    #[cfg(not(feature = "profile"))]
+    {
+        let _ = day;
+        let start = Instant::now();
+        while start.elapsed() < profiler::DURATION {
+            std::hint::black_box(func(input.clone()));
+        }
+    }
 }
 
 fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
@@ -127,6 +215,13 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
     }
 }
 
+/// Warn about answers that were lost to a narrowing conversion via [`crate::narrow`].
+fn print_narrowing_warnings(part: &str) {
+    for warning in answer::take_narrowing_warnings() {
+        eprintln!("{ANSI_BOLD}Warning:{ANSI_RESET} {part}: {warning}");
+    }
+}
+
 /// Parse the arguments passed to `solve` and try to submit one part of the solution if:
 ///  1. we are in `--release` mode.
 ///  2. aoc-cli is installed.
@@ -163,5 +258,209 @@ fn submit_result<T: Display>(
     }
 
     println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    let result = result.to_string();
+    let output = aoc_cli::submit(day, part, &result);
+
+    let response = match &output {
+        Ok(output) | Err(aoc_cli::AocCommandError::BadExitStatus(output)) => output,
+        Err(_) => return Some(output),
+    };
+
+    let verdict = Verdict::from_response(&String::from_utf8_lossy(&response.stdout));
+    if let Err(e) = submissions::append(&Submission::new(day, part, &result, verdict)) {
+        eprintln!("Failed to log submission: {e}");
+    }
+
+    if verdict == Verdict::Correct {
+        // part two is only part of the description once part one has been solved.
+        if part == 1 {
+            match cache::refresh_description(day) {
+                Ok(_) => println!("Updated the puzzle description with part two."),
+                Err(e) => eprintln!("Failed to update the puzzle description: {e}"),
+            }
+        }
+
+        match readme_stars::refresh() {
+            Ok(()) => println!("Updated README with ⭐️ progress."),
+            Err(e) => eprintln!("Failed to update README with ⭐️ progress: {e}"),
+        }
+    }
+
+    Some(output)
+}
+
+/// Extracts the answers of both parts from the output of a solution binary, as printed by
+/// [`run_part`]. Used by commands that run solutions as child processes.
+pub fn parse_answers(output: &str) -> [Option<String>; 2] {
+    let mut answers = [None, None];
+    let mut lines = output.lines().peekable();
+
+    while let Some(line) = lines.next() {
+        // intermediate results are overwritten with `\r` once a part has finished.
+        let line = strip_ansi(line.rsplit('\r').next().unwrap_or_default());
+
+        let Some((part, rest)) = line.split_once(": ") else {
+            continue;
+        };
+
+        let index = match part {
+            "Part 1" => 0,
+            "Part 2" => 1,
+            _ => continue,
+        };
+
+        let answer = rest.split(" (").next().unwrap_or_default().trim();
+
+        answers[index] = match answer {
+            "✖" => None,
+            // multi-line answers are printed below the part.
+            "▼" => {
+                let mut block = vec![];
+                while let Some(next) = lines.next_if(|next| !next.contains("Part 2: ")) {
+                    block.push(next);
+                }
+                Some(block.join("\n"))
+            }
+            answer => Some(answer.to_string()),
+        };
+    }
+
+    answers
+}
+
+/// Extracts the execution times of both parts from the output of a solution binary, e.g. `1.2ms`
+/// for `Part 1: 42 (1.2ms @ 100 samples)`.
+pub fn parse_timings(output: &str) -> [Option<String>; 2] {
+    let mut timings = [None, None];
+
+    for line in output.lines() {
+        let line = strip_ansi(line.rsplit('\r').next().unwrap_or_default());
+
+        let index = if line.starts_with("Part 1: ") {
+            0
+        } else if line.starts_with("Part 2: ") {
+            1
+        } else {
+            continue;
+        };
+
+        // allocation stats follow the timing in brackets.
+        let line = match line.rsplit_once(" [") {
+            Some((line, stats)) if stats.ends_with(']') => line,
+            _ => &line,
+        };
+
+        timings[index] = line
+            .strip_suffix(')')
+            .and_then(|line| line.rsplit_once(" ("))
+            .map(|(_, timing)| {
+                timing
+                    .split('@')
+                    .next()
+                    .unwrap_or_default()
+                    .trim()
+                    .to_string()
+            });
+    }
+
+    timings
+}
+
+/// Extracts the allocation stats of both parts from the output of a solution binary that was
+/// built with the `alloc_stats` feature.
+pub fn parse_alloc_stats(output: &str) -> [Option<alloc_stats::Stats>; 2] {
+    let mut stats = [None, None];
+
+    for line in output.lines() {
+        let line = strip_ansi(line.rsplit('\r').next().unwrap_or_default());
+
+        let index = if line.starts_with("Part 1: ") {
+            0
+        } else if line.starts_with("Part 2: ") {
+            1
+        } else {
+            continue;
+        };
+
+        stats[index] = line
+            .trim_end()
+            .strip_suffix(']')
+            .and_then(|line| line.rsplit_once(" ["))
+            .and_then(|(_, x)| x.parse().ok());
+    }
+
+    stats
+}
+
+pub(crate) fn strip_ansi(s: &str) -> String {
+    let mut stripped = String::with_capacity(s.len());
+    let mut chars = s.chars();
+
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            chars.by_ref().find(char::is_ascii_alphabetic);
+        } else {
+            stripped.push(c);
+        }
+    }
+
+    stripped
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_alloc_stats, parse_answers, parse_timings, strip_ansi};
+
+    #[test]
+    fn strips_ansi_sequences() {
+        assert_eq!(
+            strip_ansi("Part 1: \x1b[1m142\x1b[0m (1.2µs)"),
+            "Part 1: 142 (1.2µs)"
+        );
+    }
+
+    #[test]
+    fn parses_answers() {
+        let output = "Part 1: \x1b[1m142\x1b[0m\rPart 1: \x1b[1m142\x1b[0m (13.4µs)\nPart 2: ✖\rPart 2: ✖             \n";
+        assert_eq!(parse_answers(output), [Some("142".into()), None]);
+    }
+
+    #[test]
+    fn parses_timed_answers() {
+        let output = "Part 1: \x1b[1m1\x1b[0m > \x1b[3mbenching\x1b[0m\rPart 1: \x1b[1m1\x1b[0m (1.0ms @ 10 samples)\nPart 2: \x1b[1m-2\x1b[0m (2.0ms @ 10 samples)\n";
+        assert_eq!(parse_answers(output), [Some("1".into()), Some("-2".into())]);
+    }
+
+    #[test]
+    fn parses_multiline_answers() {
+        let output = "Part 1: ▼ \rPart 1: ▼  (1.0ms)\n#.\n.#\nPart 2: \x1b[1m7\x1b[0m (1.0ms)\n";
+        assert_eq!(
+            parse_answers(output),
+            [Some("#.\n.#".into()), Some("7".into())]
+        );
+    }
+
+    #[test]
+    fn parses_timings() {
+        let output = "Part 1: \x1b[1m1\x1b[0m > \x1b[3mbenching\x1b[0m\rPart 1: \x1b[1m1\x1b[0m (1.0ms @ 10 samples)\nPart 2: \x1b[1m-2\x1b[0m (13.4µs)\n";
+        assert_eq!(
+            parse_timings(output),
+            [Some("1.0ms".into()), Some("13.4µs".into())]
+        );
+        assert_eq!(parse_timings("Part 1: ✖             \n"), [None, None]);
+    }
+
+    #[test]
+    fn parses_alloc_stats() {
+        let output = "Part 1: \x1b[1m1\x1b[0m (1.0ms @ 10 samples) [peak 1.5 KiB, total 3.0 KiB, 4 allocations]\nPart 2: \x1b[1m-2\x1b[0m (13.4µs)\n";
+        assert_eq!(
+            parse_timings(output),
+            [Some("1.0ms".into()), Some("13.4µs".into())]
+        );
+
+        let stats = parse_alloc_stats(output);
+        assert_eq!(stats[0].map(|x| x.peak_bytes), Some(1536));
+        assert_eq!(stats[0].map(|x| x.allocations), Some(4));
+        assert_eq!(stats[1], None);
+    }
 }
@@ -0,0 +1,238 @@
+/// Local log of submitted answers, `data/submissions.log`. Every submission made with
+/// `solve --submit` is appended together with the verdict of the website, so progress can be
+/// tracked without asking the website.
+use std::fmt::Display;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::Day;
+
+pub const LOG_FILE: &str = "data/submissions.log";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    Wrong,
+    TooHigh,
+    TooLow,
+    /// The answer was submitted too soon after the previous one and was not checked.
+    TooRecent,
+    /// The part had already been solved, the answer was not checked.
+    AlreadySolved,
+    Unknown,
+}
+
+impl Verdict {
+    /// Reads the verdict from the response of the website, as printed by aoc-cli.
+    pub fn from_response(response: &str) -> Self {
+        if response.contains("That's the right answer") {
+            Self::Correct
+        } else if response.contains("too high") {
+            Self::TooHigh
+        } else if response.contains("too low") {
+            Self::TooLow
+        } else if response.contains("That's not the right answer") {
+            Self::Wrong
+        } else if response.contains("answer too recently") {
+            Self::TooRecent
+        } else if response.contains("Did you already complete it?") {
+            Self::AlreadySolved
+        } else {
+            Self::Unknown
+        }
+    }
+
+    /// Whether the part is solved after a submission with this verdict.
+    pub fn is_solved(self) -> bool {
+        matches!(self, Self::Correct | Self::AlreadySolved)
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let verdict = match self {
+            Self::Correct => "correct",
+            Self::Wrong => "wrong",
+            Self::TooHigh => "too-high",
+            Self::TooLow => "too-low",
+            Self::TooRecent => "too-recent",
+            Self::AlreadySolved => "already-solved",
+            Self::Unknown => "unknown",
+        };
+        write!(f, "{verdict}")
+    }
+}
+
+impl FromStr for Verdict {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "correct" => Ok(Self::Correct),
+            "wrong" => Ok(Self::Wrong),
+            "too-high" => Ok(Self::TooHigh),
+            "too-low" => Ok(Self::TooLow),
+            "too-recent" => Ok(Self::TooRecent),
+            "already-solved" => Ok(Self::AlreadySolved),
+            "unknown" => Ok(Self::Unknown),
+            _ => Err(format!("unknown verdict \"{s}\"")),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Submission {
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+    pub day: Day,
+    pub part: u8,
+    pub answer: String,
+    pub verdict: Verdict,
+}
+
+impl Submission {
+    pub fn new(day: Day, part: u8, answer: &str, verdict: Verdict) -> Self {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |x| x.as_secs());
+
+        Self {
+            timestamp,
+            day,
+            part,
+            answer: answer.to_string(),
+            verdict,
+        }
+    }
+}
+
+/// Lines are tab-separated: time, day, part, verdict, answer. The answer goes last, as it is the
+/// only field that may contain spaces.
+impl Display for Submission {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}\t{}\t{}\t{}\t{}",
+            self.timestamp,
+            self.day,
+            self.part,
+            self.verdict,
+            self.answer.replace('\n', "\\n")
+        )
+    }
+}
+
+impl FromStr for Submission {
+    type Err = String;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let fields = line.splitn(5, '\t').collect::<Vec<_>>();
+        let [timestamp, day, part, verdict, answer] = fields[..] else {
+            return Err(format!("expected 5 fields, found {}", fields.len()));
+        };
+
+        Ok(Self {
+            timestamp: timestamp.parse().map_err(|_| "invalid time")?,
+            day: day.parse().map_err(|_| "invalid day")?,
+            part: part.parse().map_err(|_| "invalid part")?,
+            verdict: verdict.parse()?,
+            answer: answer.replace("\\n", "\n"),
+        })
+    }
+}
+
+pub fn append(submission: &Submission) -> io::Result<()> {
+    let mut log = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(LOG_FILE)?;
+    writeln!(log, "{submission}")
+}
+
+/// Reads all submissions. A missing log is treated as empty.
+pub fn load() -> Result<Vec<Submission>, String> {
+    let log = match fs::read_to_string(LOG_FILE) {
+        Ok(log) => log,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+        Err(e) => return Err(format!("could not read \"{LOG_FILE}\": {e}")),
+    };
+
+    parse(&log)
+}
+
+fn parse(log: &str) -> Result<Vec<Submission>, String> {
+    log.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            line.parse()
+                .map_err(|e| format!("{LOG_FILE}:{}: {e}", i + 1))
+        })
+        .collect()
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse, Submission, Verdict};
+    use crate::day;
+
+    #[test]
+    fn reads_verdicts() {
+        assert_eq!(
+            Verdict::from_response("That's the right answer! You are one gold star closer."),
+            Verdict::Correct
+        );
+        assert_eq!(
+            Verdict::from_response("That's not the right answer; your answer is too high."),
+            Verdict::TooHigh
+        );
+        assert_eq!(
+            Verdict::from_response("That's not the right answer."),
+            Verdict::Wrong
+        );
+        assert_eq!(
+            Verdict::from_response(
+                "You don't seem to be solving the right level.  Did you already complete it?"
+            ),
+            Verdict::AlreadySolved
+        );
+        assert_eq!(Verdict::from_response(""), Verdict::Unknown);
+    }
+
+    #[test]
+    fn round_trips_log() {
+        let submissions = vec![
+            Submission {
+                timestamp: 1_701_407_112,
+                day: day!(1),
+                part: 1,
+                answer: "54159".into(),
+                verdict: Verdict::Correct,
+            },
+            Submission {
+                timestamp: 1_701_407_200,
+                day: day!(13),
+                part: 2,
+                answer: "#.\n.#".into(),
+                verdict: Verdict::TooLow,
+            },
+        ];
+
+        let log = submissions
+            .iter()
+            .map(|x| format!("{x}\n"))
+            .collect::<String>();
+        assert!(log.starts_with("1701407112\t01\t1\tcorrect\t54159\n"));
+        assert_eq!(parse(&log).unwrap(), submissions);
+    }
+
+    #[test]
+    fn reports_invalid_lines() {
+        assert_eq!(
+            parse("1701407112\t01\t1\tcorrect\t1\n\nnope\n").unwrap_err(),
+            "data/submissions.log:3: expected 5 fields, found 1"
+        );
+    }
+}
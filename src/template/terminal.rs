@@ -0,0 +1,113 @@
+/// Helpers for interactive terminal output, shared by the visualisation player and the dashboard.
+use std::fs::File;
+use std::io::{self, BufReader, Read, Write};
+use std::process::{Command, Stdio};
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+
+pub(crate) const ANSI_CLEAR: &str = "\x1b[2J\x1b[H";
+pub(crate) const ANSI_HIDE_CURSOR: &str = "\x1b[?25l";
+pub(crate) const ANSI_SHOW_CURSOR: &str = "\x1b[?25h";
+pub(crate) const ANSI_ALTERNATE_SCREEN: &str = "\x1b[?1049h";
+pub(crate) const ANSI_MAIN_SCREEN: &str = "\x1b[?1049l";
+pub(crate) const ANSI_REVERSE: &str = "\x1b[7m";
+
+/// Byte read for Ctrl-C in raw mode, where it does not send `SIGINT`.
+pub(crate) const CTRL_C: u8 = 0x03;
+
+/// Puts the controlling terminal into non-canonical mode so single key presses can be read.
+/// Signals are disabled as well, so that Ctrl-C is read as [`CTRL_C`] instead of killing the
+/// process before the terminal is restored. Returns the previous settings, or `None` if there is
+/// no terminal to read keys from.
+pub(crate) fn enter_raw_mode() -> Option<String> {
+    let stty = |args: &[&str]| {
+        Command::new("stty")
+            .args(args)
+            .stdin(File::open("/dev/tty").ok()?)
+            .stderr(Stdio::null())
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+    };
+
+    let saved = stty(&["-g"])?;
+    stty(&["-icanon", "-echo", "-isig", "min", "1"])?;
+    Some(String::from_utf8_lossy(&saved.stdout).trim().to_string())
+}
+
+pub(crate) fn restore_mode(saved: &str) {
+    if let Ok(tty) = File::open("/dev/tty") {
+        let _ = Command::new("stty").arg(saved).stdin(tty).status();
+    }
+}
+
+/// The alternate screen with a hidden cursor, with the terminal in raw mode if possible. Dropping it
+/// restores the screen, the cursor and the terminal mode, so the terminal is also restored if the
+/// caller fails.
+pub(crate) struct Screen<W: Write> {
+    out: W,
+    /// Terminal settings before raw mode, `None` if there is no terminal to read keys from.
+    pub saved_mode: Option<String>,
+}
+
+impl<W: Write> Screen<W> {
+    pub fn enter(out: W) -> io::Result<Self> {
+        let mut screen = Self {
+            out,
+            saved_mode: enter_raw_mode(),
+        };
+
+        write!(screen.out, "{ANSI_ALTERNATE_SCREEN}{ANSI_HIDE_CURSOR}")?;
+        screen.out.flush()?;
+        Ok(screen)
+    }
+}
+
+impl<W: Write> Drop for Screen<W> {
+    fn drop(&mut self) {
+        let _ = write!(self.out, "{ANSI_SHOW_CURSOR}{ANSI_MAIN_SCREEN}");
+        let _ = self.out.flush();
+
+        if let Some(saved_mode) = &self.saved_mode {
+            restore_mode(saved_mode);
+        }
+    }
+}
+
+/// Size of the terminal as `(rows, columns)`.
+pub(crate) fn size() -> Option<(usize, usize)> {
+    let output = Command::new("stty")
+        .arg("size")
+        .stdin(File::open("/dev/tty").ok()?)
+        .output()
+        .ok()?;
+
+    let size = String::from_utf8_lossy(&output.stdout);
+    let (rows, columns) = size.trim().split_once(' ')?;
+    Some((rows.parse().ok()?, columns.parse().ok()?))
+}
+
+/// Reads key presses from the terminal on a background thread. `decode` turns the bytes read
+/// from the terminal into keys; it may keep state to decode escape sequences.
+pub(crate) fn spawn_key_reader<K: Send + 'static>(
+    mut decode: impl FnMut(u8) -> Option<K> + Send + 'static,
+) -> Option<Receiver<K>> {
+    let tty = File::open("/dev/tty").ok()?;
+    let (tx, rx) = mpsc::channel();
+
+    thread::spawn(move || {
+        for byte in BufReader::new(tty).bytes() {
+            let Ok(byte) = byte else { break };
+
+            let Some(key) = decode(byte) else {
+                continue;
+            };
+
+            if tx.send(key).is_err() {
+                break;
+            }
+        }
+    });
+
+    Some(rx)
+}
@@ -0,0 +1,196 @@
+/// Opt-in debug output for solutions.
+/// Tracing is disabled by default. It is enabled by passing `--trace` to `cargo solve` and writes
+/// to stderr or to the file passed via `--trace-file`, so stdout only ever contains the answers.
+use std::fmt::{Arguments, Display};
+use std::fs::File;
+use std::io::{self, BufWriter, IsTerminal, Write};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+use std::sync::Mutex;
+use std::{env, process};
+
+use crate::template::runner;
+
+/// Verbosity of a trace message. Enabling a level also enables all less verbose levels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Info = 1,
+    Debug = 2,
+    Trace = 3,
+}
+
+impl Display for Level {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Level::Info => write!(f, "info"),
+            Level::Debug => write!(f, "debug"),
+            Level::Trace => write!(f, "trace"),
+        }
+    }
+}
+
+impl FromStr for Level {
+    type Err = LevelFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "info" => Ok(Level::Info),
+            "debug" => Ok(Level::Debug),
+            "trace" => Ok(Level::Trace),
+            _ => Err(LevelFromStrError),
+        }
+    }
+}
+
+/// An error which can be returned when parsing a [`Level`].
+#[derive(Debug)]
+pub struct LevelFromStrError;
+
+impl std::error::Error for LevelFromStrError {}
+
+impl Display for LevelFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting one of \"info\", \"debug\" or \"trace\"")
+    }
+}
+
+/// The most verbose enabled level, `0` while tracing is off.
+static MAX_LEVEL: AtomicU8 = AtomicU8::new(0);
+static SINK: Mutex<Option<Box<dyn Write + Send>>> = Mutex::new(None);
+static COLOURS: AtomicBool = AtomicBool::new(false);
+
+/// Enables tracing up to `level`. Output goes to `file` if given, otherwise to stderr.
+pub fn init(level: Level, file: Option<&str>) -> io::Result<()> {
+    let (sink, colours): (Box<dyn Write + Send>, bool) = match file {
+        Some(path) => (Box::new(BufWriter::new(File::create(path)?)), false),
+        None => (
+            Box::new(io::stderr()),
+            crate::colours_enabled(io::stderr().is_terminal()),
+        ),
+    };
+
+    *SINK.lock().unwrap() = Some(sink);
+    COLOURS.store(colours, Ordering::Relaxed);
+    MAX_LEVEL.store(level as u8, Ordering::Relaxed);
+    Ok(())
+}
+
+/// Enables tracing if the solution binary was invoked with `--trace`, `--trace-level <level>` or
+/// `--trace-file <path>`. Called by the `solution!` macro before running the parts.
+pub fn init_from_args() {
+    let args: Vec<String> = env::args().collect();
+
+    let value_of = |flag: &str| {
+        args.iter()
+            .position(|x| x == flag)
+            .and_then(|i| args.get(i + 1))
+    };
+
+    let level = match value_of("--trace-level").map(|x| x.parse::<Level>()) {
+        Some(Ok(level)) => Some(level),
+        Some(Err(e)) => {
+            eprintln!("Unexpected value for --trace-level: {e}.");
+            process::exit(1);
+        }
+        None => None,
+    };
+
+    let file = value_of("--trace-file");
+
+    if level.is_none() && file.is_none() && !args.contains(&"--trace".into()) {
+        return;
+    }
+
+    if let Err(e) = init(level.unwrap_or(Level::Trace), file.map(String::as_str)) {
+        eprintln!("Failed to open trace file: {e}");
+        process::exit(1);
+    }
+}
+
+/// Returns whether messages at `level` are written. Check this before building expensive output.
+/// Like frames of a visualisation, messages are only written during the first execution of a part,
+/// so `--time` does not repeat them for every benchmark iteration.
+#[inline]
+pub fn enabled(level: Level) -> bool {
+    level as u8 <= MAX_LEVEL.load(Ordering::Relaxed) && runner::current_part().is_some()
+}
+
+/// Returns whether trace output may contain colours, see [`crate::Grid::fmt_with_styles`]. Trace
+/// files never contain colours.
+pub fn colours() -> bool {
+    COLOURS.load(Ordering::Relaxed)
+}
+
+// Not part of the public API, use the `info!`, `debug!` and `trace!` macros instead.
+#[doc(hidden)]
+pub fn write(level: Level, args: Arguments) {
+    if let Some(sink) = SINK.lock().unwrap().as_mut() {
+        let message = args.to_string();
+
+        // keep multi-line output such as grids aligned by starting it on its own line.
+        let _ = if message.contains('\n') {
+            writeln!(sink, "[{level}]\n{}", message.trim_end_matches('\n'))
+        } else {
+            writeln!(sink, "[{level}] {message}")
+        };
+        let _ = sink.flush();
+    }
+}
+
+/// Writes a message at [`Level::Info`] if tracing is enabled.
+#[macro_export]
+macro_rules! info {
+    ($($arg:tt)+) => {
+        if $crate::template::trace::enabled($crate::template::trace::Level::Info) {
+            $crate::template::trace::write(
+                $crate::template::trace::Level::Info,
+                format_args!($($arg)+),
+            );
+        }
+    };
+}
+
+/// Writes a message at [`Level::Debug`] if tracing is enabled.
+#[macro_export]
+macro_rules! debug {
+    ($($arg:tt)+) => {
+        if $crate::template::trace::enabled($crate::template::trace::Level::Debug) {
+            $crate::template::trace::write(
+                $crate::template::trace::Level::Debug,
+                format_args!($($arg)+),
+            );
+        }
+    };
+}
+
+/// Writes a message at [`Level::Trace`] if tracing is enabled.
+#[macro_export]
+macro_rules! trace {
+    ($($arg:tt)+) => {
+        if $crate::template::trace::enabled($crate::template::trace::Level::Trace) {
+            $crate::template::trace::write(
+                $crate::template::trace::Level::Trace,
+                format_args!($($arg)+),
+            );
+        }
+    };
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Level;
+
+    #[test]
+    fn parses_levels() {
+        assert_eq!("info".parse::<Level>().unwrap(), Level::Info);
+        assert_eq!("DEBUG".parse::<Level>().unwrap(), Level::Debug);
+        assert_eq!("trace".parse::<Level>().unwrap(), Level::Trace);
+        assert!("verbose".parse::<Level>().is_err());
+    }
+
+    #[test]
+    fn orders_levels_by_verbosity() {
+        assert!(Level::Info < Level::Debug);
+        assert!(Level::Debug < Level::Trace);
+    }
+}
@@ -0,0 +1,295 @@
+/// Records frames of a solution's state (usually a [`crate::Grid`]) and plays them back in the
+/// terminal once the part has finished.
+/// Recording is disabled unless the solution binary is invoked with `--visualize` or
+/// `--visualize-export <path>`, in which case the frames are also written to an asciicast file.
+use std::fmt::Display;
+use std::fs;
+use std::io::{self, IsTerminal, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{Receiver, RecvTimeoutError};
+use std::sync::Mutex;
+use std::time::Duration;
+use std::{env, thread};
+
+use crate::template::terminal::{self, Screen, ANSI_CLEAR};
+use crate::template::{runner, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use crate::Day;
+
+/// Delay between two frames at 1x speed.
+const FRAME_DELAY: Duration = Duration::from_millis(100);
+
+pub struct Frame {
+    pub part: u8,
+    pub content: String,
+}
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static COLOURS: AtomicBool = AtomicBool::new(false);
+static FRAMES: Mutex<Vec<Frame>> = Mutex::new(Vec::new());
+
+/// Returns whether frames are currently recorded. Check this before computing expensive frames.
+#[inline]
+pub fn recording() -> bool {
+    ENABLED.load(Ordering::Relaxed) && runner::current_part().is_some()
+}
+
+/// Records one frame, e.g. a `Grid` or the result of `Grid::fmt_with_overrides`.
+/// The frame is only formatted if recording is enabled.
+pub fn record(frame: impl Display) {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return;
+    }
+
+    let Some(part) = runner::current_part() else {
+        return;
+    };
+
+    FRAMES.lock().unwrap().push(Frame {
+        part,
+        content: frame.to_string(),
+    });
+}
+
+/// Enables recording if the solution binary was invoked with `--visualize` or
+/// `--visualize-export <path>`. Called by the `solution!` macro before running the parts.
+pub fn init_from_args() {
+    let args: Vec<String> = env::args().collect();
+    let enabled = args
+        .iter()
+        .any(|x| x == "--visualize" || x == "--visualize-export");
+    ENABLED.store(enabled, Ordering::Relaxed);
+
+    let exporting = args.iter().any(|x| x == "--visualize-export");
+    let colours = !exporting && crate::colours_enabled(io::stderr().is_terminal());
+    COLOURS.store(colours, Ordering::Relaxed);
+}
+
+/// Returns whether frames may contain colours, see [`crate::Grid::fmt_with_styles`]. Frames are
+/// played back on stderr, and never contain colours when they are exported to a file.
+pub fn colours() -> bool {
+    COLOURS.load(Ordering::Relaxed)
+}
+
+/// Plays back and / or exports all recorded frames, depending on the arguments passed to the
+/// solution binary. Called by the `solution!` macro after running the parts.
+pub fn finish(day: Day) {
+    let frames = std::mem::take(&mut *FRAMES.lock().unwrap());
+
+    if !ENABLED.load(Ordering::Relaxed) {
+        return;
+    }
+
+    if frames.is_empty() {
+        eprintln!("No frames were recorded. Use `advent_of_code::template::visualize::record` in your solution.");
+        return;
+    }
+
+    let args: Vec<String> = env::args().collect();
+
+    if let Some(path) = args
+        .iter()
+        .position(|x| x == "--visualize-export")
+        .and_then(|i| args.get(i + 1))
+    {
+        match export_asciicast(path, day, &frames) {
+            Ok(()) => eprintln!("Exported {} frames to \"{path}\".", frames.len()),
+            Err(e) => eprintln!("Failed to export frames: {e}"),
+        }
+    }
+
+    if args.contains(&"--visualize".into()) {
+        if let Err(e) = play(day, &frames) {
+            eprintln!("Failed to play back frames: {e}");
+        }
+    }
+}
+
+fn status_line(day: Day, frames: &[Frame], index: usize, speed: f64, paused: bool) -> String {
+    format!(
+        "{ANSI_BOLD}Day {day} · Part {}{ANSI_RESET} · frame {}/{} · {speed}x{}\n{ANSI_ITALIC}space: pause · n/p: step · +/-: speed · q: quit{ANSI_RESET}\n\n",
+        frames[index].part,
+        index + 1,
+        frames.len(),
+        if paused { " · paused" } else { "" },
+    )
+}
+
+enum Key {
+    TogglePause,
+    Next,
+    Previous,
+    Faster,
+    Slower,
+    Quit,
+}
+
+/// Reads key presses from the terminal on a background thread.
+fn spawn_key_reader() -> Option<Receiver<Key>> {
+    terminal::spawn_key_reader(|byte| match byte {
+        b' ' => Some(Key::TogglePause),
+        b'n' | b'l' => Some(Key::Next),
+        b'p' | b'h' => Some(Key::Previous),
+        b'+' | b'=' => Some(Key::Faster),
+        b'-' => Some(Key::Slower),
+        b'q' | 0x1b | terminal::CTRL_C => Some(Key::Quit),
+        _ => None,
+    })
+}
+
+/// Plays back frames on the alternate screen, so the answers stay visible afterwards.
+fn play(day: Day, frames: &[Frame]) -> io::Result<()> {
+    let screen = Screen::enter(io::stderr())?;
+    let keys = screen.saved_mode.as_ref().and_then(|_| spawn_key_reader());
+
+    let mut stderr = io::stderr();
+
+    let mut index = 0;
+    let mut speed = 1.0;
+    let mut paused = false;
+
+    loop {
+        write!(
+            stderr,
+            "{ANSI_CLEAR}{}{}",
+            status_line(day, frames, index, speed, paused),
+            frames[index].content
+        )?;
+        stderr.flush()?;
+
+        let at_end = index + 1 == frames.len();
+
+        let key = match &keys {
+            // without a terminal, play through once.
+            None if at_end => break,
+            None => {
+                thread::sleep(FRAME_DELAY);
+                None
+            }
+            Some(keys) if paused || at_end => Some(keys.recv().unwrap_or(Key::Quit)),
+            Some(keys) => match keys.recv_timeout(FRAME_DELAY.div_f64(speed)) {
+                Ok(key) => Some(key),
+                Err(RecvTimeoutError::Timeout) => None,
+                Err(RecvTimeoutError::Disconnected) => break,
+            },
+        };
+
+        match key {
+            None if paused || at_end => {}
+            None => index += 1,
+            Some(Key::TogglePause) => paused = !paused,
+            Some(Key::Next) => {
+                paused = true;
+                index = (index + 1).min(frames.len() - 1);
+            }
+            Some(Key::Previous) => {
+                paused = true;
+                index = index.saturating_sub(1);
+            }
+            Some(Key::Faster) => speed = (speed * 2.0_f64).min(64.0),
+            Some(Key::Slower) => speed = (speed / 2.0_f64).max(0.125),
+            Some(Key::Quit) => break,
+        }
+    }
+
+    Ok(())
+}
+
+fn escape_json(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\r\\n"),
+            '\r' => {}
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+/// Renders the frames as an [asciicast v2](https://docs.asciinema.org/manual/asciicast/v2/) file.
+fn to_asciicast(day: Day, frames: &[Frame]) -> String {
+    let width = frames
+        .iter()
+        .flat_map(|frame| frame.content.lines())
+        .map(|line| line.chars().count())
+        .max()
+        .unwrap_or(0)
+        .max(40);
+    let height = frames
+        .iter()
+        .map(|frame| frame.content.lines().count())
+        .max()
+        .unwrap_or(0)
+        + 2;
+
+    let mut lines = vec![format!(
+        "{{\"version\": 2, \"width\": {width}, \"height\": {height}, \"title\": \"Day {day}\"}}"
+    )];
+
+    for (i, frame) in frames.iter().enumerate() {
+        let content = format!(
+            "{ANSI_CLEAR}Day {day} · Part {} · frame {}/{}\n\n{}",
+            frame.part,
+            i + 1,
+            frames.len(),
+            frame.content
+        );
+
+        lines.push(format!(
+            "[{:.3}, \"o\", \"{}\"]",
+            FRAME_DELAY.as_secs_f64() * i as f64,
+            escape_json(&content)
+        ));
+    }
+
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+fn export_asciicast(path: &str, day: Day, frames: &[Frame]) -> io::Result<()> {
+    fs::write(path, to_asciicast(day, frames))
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{escape_json, to_asciicast, Frame};
+    use crate::day;
+
+    #[test]
+    fn escapes_json() {
+        assert_eq!(escape_json("a\"b\\c\nd\x1b"), "a\\\"b\\\\c\\r\\nd\\u001b");
+    }
+
+    #[test]
+    fn renders_asciicast() {
+        let frames = vec![
+            Frame {
+                part: 1,
+                content: "..\n.#\n".into(),
+            },
+            Frame {
+                part: 1,
+                content: "O.\n.#\n".into(),
+            },
+        ];
+
+        let cast = to_asciicast(day!(14), &frames);
+        let lines: Vec<&str> = cast.lines().collect();
+
+        assert_eq!(lines.len(), 3);
+        assert_eq!(
+            lines[0],
+            "{\"version\": 2, \"width\": 40, \"height\": 4, \"title\": \"Day 14\"}"
+        );
+        assert_eq!(
+            lines[2],
+            "[0.100, \"o\", \"\\u001b[2J\\u001b[HDay 14 · Part 1 · frame 2/2\\r\\n\\r\\nO.\\r\\n.#\\r\\n\"]"
+        );
+    }
+}